@@ -1,7 +1,77 @@
 //! Derive macros for the [`Queryable`] and [`Table`] traits of the `rejis` crate.
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
-use syn::{Data, DeriveInput, Fields, Visibility};
+use syn::{Attribute, Data, DeriveInput, Field, Fields, LitStr, Visibility};
+
+/// Options set using `#[rejis(..)]` attributes on a struct field.
+#[derive(Default)]
+struct FieldOptions {
+    /// Field uniquely identifies the object within its table.
+    key: bool,
+    /// Field is indexed by the table.
+    index: bool,
+    /// Field is materialized as a stored generated column of the table.
+    generated: bool,
+}
+
+fn field_options(field: &Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("rejis"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                options.key = true;
+                Ok(())
+            } else if meta.path.is_ident("index") {
+                options.index = true;
+                Ok(())
+            } else if meta.path.is_ident("generated") {
+                options.generated = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported rejis attribute"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
+/// Options set using `#[rejis(..)]` attributes on a struct.
+#[derive(Default)]
+struct TableOptions {
+    /// Path of a function returning the migrations of the table.
+    migrations: Option<syn::Path>,
+    /// Path of a function returning the upgrades of the table.
+    upgrades: Option<syn::Path>,
+}
+
+fn table_options(attrs: &[Attribute]) -> syn::Result<TableOptions> {
+    let mut options = TableOptions::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("rejis")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("migrations") {
+                let path: LitStr = meta.value()?.parse()?;
+                options.migrations = Some(path.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("upgrades") {
+                let path: LitStr = meta.value()?.parse()?;
+                options.upgrades = Some(path.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported rejis attribute"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
 fn impl_query_type(vis: &Visibility, ident: &Ident, fields: &Fields) -> TokenStream {
     let query_ident = Ident::new(&format!("{ident}Query"), ident.span());
 
@@ -56,7 +126,12 @@ pub fn derive_queryable(stream: proc_macro::TokenStream) -> proc_macro::TokenStr
     let derive: DeriveInput = syn::parse2(stream).unwrap();
 
     let Data::Struct(data) = derive.data else {
-        panic!("Queryable can only be derived for structs currently.");
+        return syn::Error::new_spanned(
+            derive.ident,
+            "Queryable can only be derived for structs currently",
+        )
+        .into_compile_error()
+        .into();
     };
 
     let query_type = impl_query_type(&derive.vis, &derive.ident, &data.fields);
@@ -69,18 +144,124 @@ pub fn derive_queryable(stream: proc_macro::TokenStream) -> proc_macro::TokenStr
     .into()
 }
 
-#[proc_macro_derive(Table)]
+#[proc_macro_derive(Table, attributes(rejis))]
 pub fn derive_table(stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let stream = TokenStream::from(stream);
     let derive: DeriveInput = syn::parse2(stream).unwrap();
 
+    impl_table(derive)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn impl_table(derive: DeriveInput) -> syn::Result<TokenStream> {
+    let options = table_options(&derive.attrs)?;
     let ident = derive.ident;
     let table_name = ident.to_string().to_lowercase();
 
-    quote! {
+    // Only struct fields can be marked using attributes, but other types
+    // can still be stored in tables.
+    let fields = match &derive.data {
+        Data::Struct(data) => data
+            .fields
+            .iter()
+            .map(|field| Ok((field, field_options(field)?)))
+            .collect::<syn::Result<Vec<_>>>()?,
+        _ => Vec::new(),
+    };
+
+    let name = |field: &Field| field.ident.as_ref().unwrap().to_string();
+
+    let mut keys = fields.iter().filter(|(_, options)| options.key);
+
+    let key = keys.next().map(|(field, _)| {
+        let key = name(field);
+        quote! {
+            fn key() -> Option<::rejis::Path> {
+                Some(::rejis::Path::default().join(#key))
+            }
+        }
+    });
+
+    if let Some((field, _)) = keys.next() {
+        return Err(syn::Error::new_spanned(
+            field,
+            "only a single field can be marked as #[rejis(key)]",
+        ));
+    }
+
+    let indices: Vec<_> = fields
+        .iter()
+        .filter(|(_, options)| options.index)
+        .map(|(field, _)| name(field))
+        .collect();
+
+    let indices = (!indices.is_empty()).then(|| {
+        quote! {
+            fn indices() -> Vec<::rejis::Path> {
+                vec![#(::rejis::Path::default().join(#indices)),*]
+            }
+        }
+    });
+
+    let generated: Vec<_> = fields
+        .iter()
+        .filter(|(_, options)| options.generated)
+        .map(|(field, _)| field)
+        .collect();
+
+    // Column names are case-insensitive, and must not shadow the columns
+    // every table has, nor the rowid.
+    let mut columns: Vec<String> = ["rowid", "oid", "_rowid_", "value", "version"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    for field in &generated {
+        let column = name(field).to_lowercase();
+        if columns.contains(&column) {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("generated column `{column}` collides with another column of the table"),
+            ));
+        }
+        columns.push(column);
+    }
+
+    let generated: Vec<_> = generated.into_iter().map(|field| name(field)).collect();
+
+    let generated = (!generated.is_empty()).then(|| {
+        quote! {
+            fn generated() -> Vec<::rejis::Path> {
+                vec![#(::rejis::Path::default().join(#generated)),*]
+            }
+        }
+    });
+
+    let migrations = options.migrations.map(|migrations| {
+        quote! {
+            fn migrations() -> Vec<::rejis::migration::Migration> {
+                #migrations()
+            }
+        }
+    });
+
+    let upgrades = options.upgrades.map(|upgrades| {
+        quote! {
+            fn upgrades() -> Vec<::rejis::migration::Upgrade> {
+                #upgrades()
+            }
+        }
+    });
+
+    Ok(quote! {
         impl ::rejis::Table for #ident {
             const TABLE_NAME: &'static str = #table_name;
+            #key
+            #indices
+            #generated
+            #migrations
+            #upgrades
         }
-    }
-    .into()
+    })
 }
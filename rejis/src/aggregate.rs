@@ -0,0 +1,220 @@
+//! Structures used for computing aggregate values over the results of queries.
+use crate::{transform::TransformError, value, Query, QueryConstructor, Queryable, Table};
+use rusqlite::Row;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Value type of the field described by a `Query<Field, Root>`.
+pub(crate) type Inner<Field, Root> =
+    <<Field as Queryable<Root>>::QueryType as QueryConstructor<Root>>::Inner;
+
+/// Describes an aggregate function computed over all objects matched by
+/// a filter, using [`Database::aggregate`](crate::Database::aggregate), or
+/// over each group of them using [`Database::group_by`](crate::Database::group_by).
+pub trait Aggregate {
+    type Root: Table;
+    type Output;
+
+    /// Sql expression computing the aggregate over the json values in `column`.
+    fn expression(&self, column: &str) -> String;
+
+    /// Extract the computed aggregate from column `index` of `row`.
+    fn extract(&self, row: &Row, index: usize) -> Result<Self::Output, TransformError>;
+}
+
+/// Deserialize the value produced by sqlite in column `index` of `row`.
+fn deserialize<T: DeserializeOwned>(row: &Row, index: usize) -> Result<T, TransformError> {
+    Ok(serde_json::from_value(value::to_json(row.get_ref(index)?))?)
+}
+
+/// Deserialize the smallest or largest value produced by sqlite in column `index`
+/// of `row`, which `json_extract` turned into an integer if it was a boolean.
+fn deserialize_extremum<T: DeserializeOwned>(row: &Row, index: usize) -> Result<T, TransformError> {
+    let value = value::to_json(row.get_ref(index)?);
+
+    serde_json::from_value(value.clone()).or_else(|err| match value.as_i64() {
+        Some(integer @ (0 | 1)) => {
+            serde_json::from_value(Value::Bool(integer == 1)).map_err(|_| err.into())
+        }
+        _ => Err(err.into()),
+    })
+}
+
+/// Integer types which can be summed using [`Sum`], along with the type
+/// of their sum, which is wide enough to hold the sum of many values.
+pub trait Summable {
+    type Sum: DeserializeOwned;
+}
+
+macro_rules! summable {
+    ($($integer:ident),*) => {
+        $(
+            impl Summable for $integer {
+                type Sum = i64;
+            }
+        )*
+    };
+}
+
+summable!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Sum of the values at the `Query` path, or zero if nothing matched.
+///
+/// Sums are computed as 64-bit integers, failing if they overflow.
+pub struct Sum<'a, Field, Root>(pub &'a Query<Field, Root>)
+where
+    Field: Queryable<Root>,
+    Root: Table;
+
+impl<'a, Field, Root> Aggregate for Sum<'a, Field, Root>
+where
+    Field: Queryable<Root>,
+    Root: Table,
+    Inner<Field, Root>: Summable,
+{
+    type Root = Root;
+    type Output = <Inner<Field, Root> as Summable>::Sum;
+
+    fn expression(&self, column: &str) -> String {
+        format!(
+            "coalesce(sum(json_extract({column}, '{path}')), 0)",
+            path = self.0.path()
+        )
+    }
+
+    fn extract(&self, row: &Row, index: usize) -> Result<Self::Output, TransformError> {
+        deserialize(row, index)
+    }
+}
+
+/// Average of the values at the `Query` path, or `None` if nothing matched.
+pub struct Avg<'a, Field, Root>(pub &'a Query<Field, Root>)
+where
+    Field: Queryable<Root>,
+    Root: Table;
+
+impl<'a, Field, Root> Aggregate for Avg<'a, Field, Root>
+where
+    Field: Queryable<Root>,
+    Root: Table,
+{
+    type Root = Root;
+    type Output = Option<f64>;
+
+    fn expression(&self, column: &str) -> String {
+        format!(
+            "avg(json_extract({column}, '{path}'))",
+            path = self.0.path()
+        )
+    }
+
+    fn extract(&self, row: &Row, index: usize) -> Result<Self::Output, TransformError> {
+        Ok(row.get(index)?)
+    }
+}
+
+/// Smallest of the values at the `Query` path, or `None` if nothing matched.
+pub struct Min<'a, Field, Root>(pub &'a Query<Field, Root>)
+where
+    Field: Queryable<Root>,
+    Root: Table;
+
+impl<'a, Field, Root> Aggregate for Min<'a, Field, Root>
+where
+    Field: Queryable<Root>,
+    Root: Table,
+{
+    type Root = Root;
+    type Output = Option<Inner<Field, Root>>;
+
+    fn expression(&self, column: &str) -> String {
+        format!(
+            "min(json_extract({column}, '{path}'))",
+            path = self.0.path()
+        )
+    }
+
+    fn extract(&self, row: &Row, index: usize) -> Result<Self::Output, TransformError> {
+        deserialize_extremum(row, index)
+    }
+}
+
+/// Largest of the values at the `Query` path, or `None` if nothing matched.
+pub struct Max<'a, Field, Root>(pub &'a Query<Field, Root>)
+where
+    Field: Queryable<Root>,
+    Root: Table;
+
+impl<'a, Field, Root> Aggregate for Max<'a, Field, Root>
+where
+    Field: Queryable<Root>,
+    Root: Table,
+{
+    type Root = Root;
+    type Output = Option<Inner<Field, Root>>;
+
+    fn expression(&self, column: &str) -> String {
+        format!(
+            "max(json_extract({column}, '{path}'))",
+            path = self.0.path()
+        )
+    }
+
+    fn extract(&self, row: &Row, index: usize) -> Result<Self::Output, TransformError> {
+        deserialize_extremum(row, index)
+    }
+}
+
+/// Number of non-null values at the `Query` path.
+///
+/// Counting at the root path, as in `Count(&User::query())`, counts every object.
+pub struct Count<'a, Field, Root>(pub &'a Query<Field, Root>)
+where
+    Field: Queryable<Root>,
+    Root: Table;
+
+impl<'a, Field, Root> Aggregate for Count<'a, Field, Root>
+where
+    Field: Queryable<Root>,
+    Root: Table,
+{
+    type Root = Root;
+    type Output = usize;
+
+    fn expression(&self, column: &str) -> String {
+        format!(
+            "count(json_extract({column}, '{path}'))",
+            path = self.0.path()
+        )
+    }
+
+    fn extract(&self, row: &Row, index: usize) -> Result<Self::Output, TransformError> {
+        Ok(row.get(index)?)
+    }
+}
+
+/// Number of distinct non-null values at the `Query` path.
+pub struct CountDistinct<'a, Field, Root>(pub &'a Query<Field, Root>)
+where
+    Field: Queryable<Root>,
+    Root: Table;
+
+impl<'a, Field, Root> Aggregate for CountDistinct<'a, Field, Root>
+where
+    Field: Queryable<Root>,
+    Root: Table,
+{
+    type Root = Root;
+    type Output = usize;
+
+    fn expression(&self, column: &str) -> String {
+        format!(
+            "count(distinct json_extract({column}, '{path}'))",
+            path = self.0.path()
+        )
+    }
+
+    fn extract(&self, row: &Row, index: usize) -> Result<Self::Output, TransformError> {
+        Ok(row.get(index)?)
+    }
+}
@@ -0,0 +1,131 @@
+//! Non-blocking access to a database from async code.
+use crate::{
+    transform::{FromRow, Transform, TransformError},
+    Database, Id, Table,
+};
+use futures_channel::oneshot;
+use rusqlite::Connection;
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc,
+    thread,
+};
+
+/// Operation executed against the [`Connection`] owned by the worker thread.
+type Job = Box<dyn FnOnce(&Connection) + Send>;
+
+/// Async counterpart to [`Database`], which owns a [`Connection`] on a
+/// dedicated worker thread, so queries do not block the async executor.
+///
+/// Operations are sent to the worker as boxed jobs, and executed one at a time
+/// in the order they were issued. The returned futures resolve once the
+/// worker has finished the operation, and do not need to be polled for the
+/// operation to make progress.
+///
+/// The worker thread exits once all clones of the `AsyncDatabase` have been dropped.
+/// Operations which panic resolve to [`TransformError::WorkerStopped`], while the
+/// worker carries on with the next operation.
+///
+/// ```rust
+/// # use rejis::{Table, Q, AsyncDatabase};
+/// # use rejis_test_data::{User, user_database};
+/// let db = AsyncDatabase::new(user_database());
+///
+/// let smiths = futures_executor::block_on(db.get(Q! { User.last_name == "Smith" })).unwrap();
+/// assert_eq!(smiths.len(), 2);
+/// ```
+#[derive(Clone)]
+pub struct AsyncDatabase {
+    jobs: mpsc::Sender<Job>,
+}
+
+impl AsyncDatabase {
+    /// Move `connection` onto a new worker thread.
+    pub fn new(connection: Connection) -> Self {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+
+        thread::spawn(move || {
+            for job in receiver {
+                // A panicking job drops its sender while unwinding, which is
+                // reported to its caller, so the worker can carry on.
+                let _ = panic::catch_unwind(AssertUnwindSafe(|| job(&connection)));
+            }
+        });
+
+        AsyncDatabase { jobs }
+    }
+
+    /// Run `f` on the worker thread, resolving to its result, or to
+    /// [`TransformError::WorkerStopped`] if `f` panicked.
+    pub fn call<R, F>(
+        &self,
+        f: F,
+    ) -> impl Future<Output = Result<R, TransformError>> + Send + 'static
+    where
+        R: Send + 'static,
+        F: FnOnce(&Connection) -> R + Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+
+        // If the worker has stopped, the job and with it the sender is dropped,
+        // which is reported when awaiting the receiver.
+        let _ = self.jobs.send(Box::new(move |connection| {
+            let _ = sender.send(f(connection));
+        }));
+
+        async move { receiver.await.map_err(|_| TransformError::WorkerStopped) }
+    }
+
+    /// Run the fallible operation `f` on the worker thread, resolving to its result.
+    fn run<R, F>(&self, f: F) -> impl Future<Output = Result<R, TransformError>> + Send + 'static
+    where
+        R: Send + 'static,
+        F: FnOnce(&Connection) -> Result<R, TransformError> + Send + 'static,
+    {
+        let call = self.call(f);
+        async move { call.await? }
+    }
+
+    /// See [`Database::init`].
+    pub fn init<Root: Table>(
+        &self,
+    ) -> impl Future<Output = Result<usize, TransformError>> + Send + 'static {
+        self.run(|connection| Ok(connection.init::<Root>()?))
+    }
+
+    /// See [`Database::insert`].
+    pub fn insert<Root: Table + Send>(
+        &self,
+        value: Root,
+    ) -> impl Future<Output = Result<Id<Root>, TransformError>> + Send + 'static {
+        self.run(move |connection| connection.insert(&value))
+    }
+
+    /// See [`Database::get`].
+    #[allow(clippy::type_complexity)]
+    pub fn get<T>(
+        &self,
+        transform: T,
+    ) -> impl Future<
+        Output = Result<Vec<<<T as Transform>::Output as FromRow>::Output>, TransformError>,
+    > + Send
+           + 'static
+    where
+        T: Transform + Send + 'static,
+        <<T as Transform>::Output as FromRow>::Output: Send,
+    {
+        self.run(move |connection| connection.get(&transform))
+    }
+
+    /// See [`Database::delete`].
+    pub fn delete<T>(
+        &self,
+        filter: T,
+    ) -> impl Future<Output = Result<usize, TransformError>> + Send + 'static
+    where
+        T: Transform + Send + 'static,
+    {
+        self.run(move |connection| connection.delete(&filter))
+    }
+}
@@ -0,0 +1,190 @@
+//! Structures used for keyset pagination of ordered queries.
+use crate::{
+    order::{Direction, Nulls, SortKey},
+    transform::{FromRow, Transform, TransformError},
+    value,
+};
+use rusqlite::{Row, Statement};
+use serde::{Deserialize, Serialize};
+
+/// Position within the results of an ordered query, as returned by
+/// [`Database::get_page`](crate::Database::get_page).
+///
+/// Captures the values of the sort keys and the `rowid` of the last item on a
+/// page, so the next page can be found by comparing against those directly,
+/// instead of skipping past all preceding items like an offset would.
+///
+/// The cursor is opaque, but can be serialized for handing out to clients.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    pub(crate) keys: Vec<serde_json::Value>,
+    pub(crate) rowid: i64,
+}
+
+/// Common direction of all `keys`, which is also used for ordering by `rowid`.
+///
+/// Keyset pagination compares all keys as a single row value, so keys
+/// ordered in different directions are not supported.
+pub(crate) fn direction(keys: &[SortKey]) -> Result<Direction, TransformError> {
+    let direction = keys.first().map_or(Direction::Asc, |key| key.direction);
+
+    if keys.iter().any(|key| key.direction != direction) {
+        return Err(TransformError::UnsupportedOrdering);
+    }
+
+    Ok(direction)
+}
+
+/// Whether `null` values of `key` are ordered before all other values, when
+/// ordering in `direction`.
+fn nulls_first(key: &SortKey, direction: Direction) -> bool {
+    match key.nulls {
+        Some(nulls) => nulls == Nulls::First,
+        // Sqlite considers null smaller than any other value.
+        None => direction == Direction::Asc,
+    }
+}
+
+/// Restricts the results of the `Inner` transform to those
+/// ordered after `cursor`, according to `keys`.
+///
+/// Row values cannot be used for comparing against the cursor, since any
+/// comparison involving `null` is itself `null`, so the comparison is
+/// expanded into one branch per key, in which all preceding keys are equal:
+///
+/// ```sql
+/// (a > ?) or (a is ? and b > ?) or (a is ? and b is ? and rowid > ?)
+/// ```
+///
+/// Where each key is compared according to its placement of `null` values.
+pub(crate) struct After<'a, Inner>
+where
+    Inner: Transform,
+{
+    pub(crate) inner: &'a Inner,
+    pub(crate) keys: &'a [SortKey],
+    pub(crate) direction: Direction,
+    pub(crate) cursor: Option<&'a Cursor>,
+}
+
+impl<'a, Inner> After<'a, Inner>
+where
+    Inner: Transform,
+{
+    /// Sql condition matching values of `key` in `column` ordered after the cursor
+    /// value `value`, which is only bound as a parameter if it is not `null`.
+    fn follows(&self, key: &SortKey, column: &str, value: &serde_json::Value) -> String {
+        let operator = match self.direction {
+            Direction::Asc => ">",
+            Direction::Desc => "<",
+        };
+
+        match (value.is_null(), nulls_first(key, self.direction)) {
+            (true, true) => format!("{column} is not null"),
+            (true, false) => String::from("false"),
+            (false, true) => format!("{column} {operator} ?"),
+            (false, false) => format!("({column} {operator} ? or {column} is null)"),
+        }
+    }
+}
+
+impl<'a, Inner> Transform for After<'a, Inner>
+where
+    Inner: Transform,
+{
+    type Root = Inner::Root;
+    type Field = Inner::Field;
+    type Output = Inner::Output;
+
+    fn bind(
+        &self,
+        statement: &mut Statement<'_>,
+        index: &mut usize,
+    ) -> Result<(), rusqlite::Error> {
+        self.inner.bind(statement, index)?;
+
+        if let Some(cursor) = self.cursor {
+            // Parameters are bound in the order they appear in the branches
+            // written by `cte`, the last of which also compares rowids.
+            for branch in 0..=cursor.keys.len() {
+                for key in &cursor.keys[..branch] {
+                    statement.raw_bind_parameter(*index, value::to_sql(key))?;
+                    *index += 1;
+                }
+
+                match cursor.keys.get(branch) {
+                    Some(key) if !key.is_null() => {
+                        statement.raw_bind_parameter(*index, value::to_sql(key))?;
+                        *index += 1;
+                    }
+                    Some(_) => {}
+                    None => {
+                        statement.raw_bind_parameter(*index, cursor.rowid)?;
+                        *index += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn columns(&self) -> String {
+        self.inner.columns()
+    }
+
+    fn is_distinct(&self) -> bool {
+        self.inner.is_distinct()
+    }
+
+    fn extract(&self, row: &Row) -> Result<<Self::Output as FromRow>::Output, TransformError> {
+        self.inner.extract(row)
+    }
+
+    fn cte(&self, name: &str, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        let Some(cursor) = self.cursor else {
+            return self.inner.cte(name, f);
+        };
+
+        self.inner.cte(&format!("{name}_unpaged"), f)?;
+
+        let columns: Vec<_> = self
+            .keys
+            .iter()
+            .map(|key| format!("json_extract({name}_unpaged.value, '{}')", key.path))
+            .collect();
+
+        let mut branches = Vec::new();
+        for branch in 0..=self.keys.len() {
+            let mut conditions: Vec<_> = columns[..branch]
+                .iter()
+                .map(|column| format!("{column} is ?"))
+                .collect();
+
+            match self.keys.get(branch) {
+                Some(key) => {
+                    conditions.push(self.follows(key, &columns[branch], &cursor.keys[branch]));
+                }
+                None => conditions.push(format!(
+                    "{name}_unpaged.rowid {operator} ?",
+                    operator = match self.direction {
+                        Direction::Asc => ">",
+                        Direction::Desc => "<",
+                    }
+                )),
+            }
+
+            branches.push(format!("({})", conditions.join(" and ")));
+        }
+
+        write!(
+            f,
+            ",\n    {name} as (
+        select {name}_unpaged.rowid, {name}_unpaged.value, {name}_unpaged.version
+        from {name}_unpaged
+        where {branches}
+    )",
+            branches = branches.join("\n        or ")
+        )
+    }
+}
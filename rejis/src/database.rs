@@ -1,21 +1,37 @@
-use rusqlite::Connection;
+use rusqlite::{
+    types::FromSql, Connection, OptionalExtension, Row, Savepoint, Statement, Transaction,
+};
 use std::fmt::Write;
 
 use crate::{
-    transform::{FromRow, Transform, TransformError},
-    Table,
+    aggregate::{Aggregate, Inner},
+    cursor::{self, After, Cursor},
+    explain::Step,
+    facet::Facet,
+    limit::Limits,
+    migration, order, table,
+    transform::{Document, FromRow, Transform, TransformError},
+    update::Update,
+    value, Id, Path, Query, Queryable, Table,
 };
 
-fn sql_query_builder(
+fn sql_query_builder<T: Transform>(
     table_name: &str,
-    filter: &impl Transform,
+    filter: &T,
     selector: &str,
 ) -> Result<String, std::fmt::Error> {
+    let generated: String = T::Root::generated()
+        .iter()
+        .map(|path| format!(", {}", path.column()))
+        .collect();
+
+    // Transforms referring to root more than once, such as And and Or, would
+    // otherwise cause it to be materialized, preventing the use of indices.
     let mut sql = format!(
         "
 with
-    root as (
-        select rowid, value
+    root as not materialized (
+        select rowid, value, version{generated}
         from {table_name}
     )",
     );
@@ -27,16 +43,450 @@ with
     Ok(sql)
 }
 
+/// Clauses appended to the final statement of `transform`, applying
+/// its ordering and the given `limits`.
+fn clauses(transform: &impl Transform, limits: &Limits) -> String {
+    format!(
+        "{order}{limit}",
+        order = order::clause(&transform.ordering()),
+        limit = limits.clause()
+    )
+}
+
+/// Selector restricting a mutation of `table` to the rows matching the
+/// `result` CTE of `filter`, with the given `limits` applied.
+fn matching_rows(table: &str, filter: &impl Transform, limits: &Limits) -> String {
+    format!(
+        "
+where {table}.rowid in (
+    select result.rowid from result{clauses}
+)",
+        clauses = clauses(filter, limits)
+    )
+}
+
+/// Fold the rows produced by `statement` over the CTEs of `transform`,
+/// with the given `limits` applied.
+fn fold_rows<T, B, E, F>(
+    connection: &Connection,
+    transform: &T,
+    statement: &str,
+    limits: Limits,
+    init: B,
+    mut f: F,
+) -> Result<B, E>
+where
+    T: Transform,
+    E: From<TransformError>,
+    F: FnMut(B, <<T as Transform>::Output as FromRow>::Output) -> Result<B, E>,
+{
+    let sql = sql_query_builder(
+        <T as Transform>::Root::TABLE_NAME,
+        transform,
+        &format!(
+            "{statement}{clauses}",
+            clauses = clauses(transform, &limits)
+        ),
+    )
+    .unwrap();
+
+    let mut stmt = connection.prepare(&sql).map_err(TransformError::from)?;
+    let mut index = 1;
+    transform
+        .bind(&mut stmt, &mut index)
+        .map_err(TransformError::from)?;
+    limits
+        .bind(&mut stmt, &mut index)
+        .map_err(TransformError::from)?;
+
+    let mut accumulator = init;
+    let mut rows = stmt.raw_query();
+    while let Some(result) = rows.next().map_err(TransformError::from)? {
+        accumulator = f(accumulator, transform.extract(result)?)?;
+    }
+
+    Ok(accumulator)
+}
+
+/// Quoted name of the index over `path` in the table of `Root`, such as
+/// `"user$.pets[0].name"`.
+///
+/// Contains the path itself rather than its identifier, so it can neither collide
+/// with the index of another path, nor with the `{table}_key` index.
+fn index_name<Root: Table>(path: &Path) -> String {
+    format!(
+        "\"{}\"",
+        format!("{}{path}", Root::TABLE_NAME).replace('"', "\"\"")
+    )
+}
+
+/// Create an index over the value at `path` in the table of `Root`, unless it already exists.
+fn create_index<Root: Table>(connection: &Connection, path: &Path) -> Result<(), rusqlite::Error> {
+    connection.execute(
+        &format!(
+            "create index if not exists {name} on {table}({expression});",
+            table = Root::TABLE_NAME,
+            name = index_name::<Root>(path),
+            expression = table::expression::<Root>(path, None)
+        ),
+        (),
+    )?;
+
+    Ok(())
+}
+
+/// Check that the generated columns declared by [`Table::generated`] neither collide
+/// with each other nor with the other columns of the table, including the rowid.
+///
+/// Fails with [`rusqlite::Error::InvalidColumnName`] naming the offending column.
+fn check_generated<Root: Table>() -> Result<(), rusqlite::Error> {
+    let mut columns: Vec<String> = ["rowid", "oid", "_rowid_", "value", "version"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    for path in Root::generated() {
+        let column = path.identifier();
+        if columns.contains(&column.to_lowercase()) {
+            return Err(rusqlite::Error::InvalidColumnName(column));
+        }
+        columns.push(column.to_lowercase());
+    }
+
+    Ok(())
+}
+
+/// Column definitions of the stored generated columns declared by [`Table::generated`].
+fn generated_columns<Root: Table>() -> String {
+    Root::generated()
+        .iter()
+        .map(|path| {
+            format!(
+                ", {name} any generated always as ({expression}) stored",
+                name = path.column(),
+                expression = path.extract("value")
+            )
+        })
+        .collect()
+}
+
+/// Recreate the table of `Root` with its current set of generated columns,
+/// since sqlite cannot add stored generated columns to existing tables.
+///
+/// Indices and triggers are dropped along with the old table, so they are
+/// recreated afterwards. Indices over paths are recreated using the current
+/// expression for their path, since they might refer to generated columns
+/// which no longer exist, while the key index is left to [`Database::init`].
+fn rebuild_table<Root: Table>(connection: &Connection) -> Result<(), rusqlite::Error> {
+    let table = Root::TABLE_NAME;
+
+    let schema = connection
+        .prepare(
+            "select name, sql from sqlite_master
+where tbl_name = ?1 and type in ('index', 'trigger') and sql is not null",
+        )?
+        .query_map((table,), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    connection.execute_batch(&format!(
+        "create table {table}_rebuild (value text not null, version integer not null default 0{columns}) strict;
+        insert into {table}_rebuild (rowid, value, version) select rowid, value, version from {table};
+        drop table {table};
+        alter table {table}_rebuild rename to {table};",
+        columns = generated_columns::<Root>()
+    ))?;
+
+    for (name, sql) in schema {
+        if name == format!("{table}_key") {
+            continue;
+        }
+
+        // Indices over paths are named after them, see `index_name`.
+        match name
+            .strip_prefix(table)
+            .filter(|path| path.starts_with('$'))
+        {
+            Some(path) => {
+                let expression = Root::generated()
+                    .into_iter()
+                    .find(|generated| generated.to_string() == path)
+                    .map(|generated| generated.column())
+                    .unwrap_or_else(|| format!("json_extract(value, '{path}')"));
+
+                connection.execute(
+                    &format!(
+                        "create index \"{name}\" on {table}({expression})",
+                        name = name.replace('"', "\"\"")
+                    ),
+                    (),
+                )?;
+            }
+            None => connection.execute_batch(&sql)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Write back the upgraded form of all outdated objects matching `filter`.
+fn upgrade_rows<T: Transform>(
+    connection: &Connection,
+    filter: &T,
+) -> Result<usize, TransformError> {
+    let table = <T as Transform>::Root::TABLE_NAME;
+    let limits = filter.limits();
+
+    let sql = sql_query_builder(
+        table,
+        filter,
+        &format!(
+            "select {table}.rowid, {table}.version, {table}.value from {table}{selector}\nand {table}.version < ?",
+            selector = matching_rows(table, filter, &limits)
+        ),
+    )
+    .unwrap();
+
+    let mut stmt = connection.prepare(&sql)?;
+    let mut index = 1;
+    filter.bind(&mut stmt, &mut index)?;
+    limits.bind(&mut stmt, &mut index)?;
+    stmt.raw_bind_parameter(index, migration::version::<T::Root>())?;
+
+    write_upgraded::<T::Root>(connection, stmt)
+}
+
+/// Write back the upgraded form of the outdated objects selected by the
+/// bound statement `stmt`, as their rowid, stored version and value.
+fn write_upgraded<Root: Table>(
+    connection: &Connection,
+    mut stmt: Statement<'_>,
+) -> Result<usize, TransformError> {
+    let version = migration::version::<Root>();
+
+    let mut outdated = Vec::new();
+    let mut rows = stmt.raw_query();
+    while let Some(row) = rows.next()? {
+        let rowid: i64 = row.get(0)?;
+        let stored: usize = row.get(1)?;
+        let value: String = row.get(2)?;
+        outdated.push((rowid, migration::upgrade::<Root>(stored, &value)?));
+    }
+
+    let mut update = connection.prepare(&format!(
+        "update {table} set value = json(?1), version = ?2 where rowid = ?3",
+        table = Root::TABLE_NAME
+    ))?;
+
+    for (rowid, value) in &outdated {
+        update.execute((serde_json::to_string(value)?, version, rowid))?;
+    }
+
+    Ok(outdated.len())
+}
+
+/// Retrieve at most `limit` of the items matching `transform`.
+fn fetch_at_most<T: Transform>(
+    connection: &Connection,
+    transform: &T,
+    limit: usize,
+) -> Result<Vec<<<T as Transform>::Output as FromRow>::Output>, TransformError> {
+    let limits = transform.limits().narrow(&Limits {
+        limit: Some(limit),
+        offset: None,
+    });
+
+    fold_rows(
+        connection,
+        transform,
+        &transform.statement(),
+        limits,
+        Vec::new(),
+        |mut objects, object| {
+            objects.push(object);
+            Ok(objects)
+        },
+    )
+}
+
+/// Wrap the final statement of `transform` using `wrapper`, and retrieve the single
+/// value produced by it.
+fn query_scalar<T: Transform, V: FromSql>(
+    connection: &Connection,
+    transform: &T,
+    wrapper: impl FnOnce(&str) -> String,
+) -> Result<V, TransformError> {
+    query_single(connection, transform, wrapper, |row| Ok(row.get(0)?))
+}
+
+/// Like [`query_scalar`], but extracting the value from the single row using `extract`.
+fn query_single<T: Transform, V>(
+    connection: &Connection,
+    transform: &T,
+    wrapper: impl FnOnce(&str) -> String,
+    extract: impl FnOnce(&Row) -> Result<V, TransformError>,
+) -> Result<V, TransformError> {
+    let limits = transform.limits();
+
+    let sql = sql_query_builder(
+        <T as Transform>::Root::TABLE_NAME,
+        transform,
+        &wrapper(&format!(
+            "{statement}{clauses}",
+            statement = transform.statement(),
+            clauses = clauses(transform, &limits)
+        )),
+    )
+    .unwrap();
+
+    let mut stmt = connection.prepare(&sql)?;
+    let mut index = 1;
+    transform.bind(&mut stmt, &mut index)?;
+    limits.bind(&mut stmt, &mut index)?;
+
+    let mut rows = stmt.raw_query();
+    let row = rows.next()?.ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+    extract(row)
+}
+
+/// Savepoint opened by [`with_savepoint`], which is rolled back when dropped
+/// without having been finished, such as when unwinding from a panic.
+struct SavepointGuard<'a>(&'a Connection);
+
+impl SavepointGuard<'_> {
+    /// Finish the savepoint by executing `sql`, which must release it.
+    fn finish(self, sql: &str) -> Result<(), rusqlite::Error> {
+        let connection = self.0;
+        std::mem::forget(self);
+        connection.execute_batch(sql)
+    }
+}
+
+impl Drop for SavepointGuard<'_> {
+    fn drop(&mut self) {
+        // Errors cannot be reported while unwinding, and leaving the
+        // savepoint open would be worse than ignoring them.
+        let _ = self.0.execute_batch("rollback to rejis; release rejis");
+    }
+}
+
+/// Run `f` within a savepoint, releasing it if `f` succeeds, or rolling
+/// back any changes made by `f` if it fails or panics.
+///
+/// Unlike transactions, savepoints can be nested, so this works regardless
+/// of whether a transaction is already active on the connection.
+pub(crate) fn with_savepoint<R, E: From<rusqlite::Error>>(
+    connection: &Connection,
+    f: impl FnOnce() -> Result<R, E>,
+) -> Result<R, E> {
+    connection.execute_batch("savepoint rejis")?;
+    let guard = SavepointGuard(connection);
+
+    match f() {
+        Ok(result) => {
+            guard.finish("release rejis")?;
+            Ok(result)
+        }
+        Err(err) => {
+            guard.finish("rollback to rejis; release rejis")?;
+            Err(err)
+        }
+    }
+}
+
+/// Provides access to the [`Connection`] used by [`Database`] operations.
+///
+/// Implemented for [`Connection`], as well as [`Transaction`] and [`Savepoint`],
+/// so operations can be grouped using either rusqlite's own transactions or
+/// [`Database::transaction`].
+pub trait AsConnection {
+    fn connection(&self) -> &Connection;
+}
+
+impl AsConnection for Connection {
+    fn connection(&self) -> &Connection {
+        self
+    }
+}
+
+impl AsConnection for Transaction<'_> {
+    fn connection(&self) -> &Connection {
+        self
+    }
+}
+
+impl AsConnection for Savepoint<'_> {
+    fn connection(&self) -> &Connection {
+        self
+    }
+}
+
 /// Simple user-friendly API for inserting, querying and deleting structures
 /// which implement both [`Queryable`](::rejis::Queryable) and [`Table`].
 pub trait Database {
-    /// Initialize a table for `Root` on the database.
+    /// Initialize a table for `Root` on the database, and apply any of its
+    /// [migrations](Table::migrations) which have not yet been applied.
+    ///
+    /// Runs within a transaction, so if any migration fails, none are applied.
     fn init<Root: Table>(&self) -> Result<usize, rusqlite::Error>;
 
+    /// Create an index over the value at the path of `query`, which speeds up
+    /// filtering by it, unless such an index already exists.
+    ///
+    /// Indices can also be declared using [`Table::indices`], in which case
+    /// they are created by [`Database::init`]. Indices are named after the
+    /// table and path, as in `user$.last_name`.
+    fn create_index<Field, Root>(&self, query: &Query<Field, Root>) -> Result<(), TransformError>
+    where
+        Field: Queryable<Root>,
+        Root: Table;
+
+    /// Run `f` within a transaction, committing its changes if it returns `Ok`,
+    /// or rolling them back if it returns `Err`.
+    ///
+    /// Uses a savepoint, so transactions can be nested, and used within an
+    /// already active [`Transaction`].
+    ///
+    /// Note that this shadows [`Connection::transaction`] while [`Database`] is
+    /// in scope, which can still be called as `Connection::transaction(&mut db)`.
+    ///
+    /// ```rust
+    /// # use rejis::{Table, Database, Q, transform::TransformError};
+    /// # use rejis_test_data::{User, user_database};
+    /// # let db = user_database();
+    /// let result: Result<(), TransformError> = db.transaction(|tx| {
+    ///     tx.delete(&Q! { User.last_name == "Smith" })?;
+    ///     Err(TransformError::NotFound)
+    /// });
+    ///
+    /// assert!(result.is_err());
+    /// assert_eq!(db.count(&Q! { User.last_name == "Smith" }).unwrap(), 2);
+    /// ```
+    fn transaction<R, E, F>(&self, f: F) -> Result<R, E>
+    where
+        E: From<rusqlite::Error>,
+        F: FnOnce(&Self) -> Result<R, E>;
+
     /// Insert `value` into the database.
     ///
     /// Note: table must already exist. Table can be created using [`Database::init`]
-    fn insert<Root: Table>(&self, value: &Root) -> Result<usize, TransformError>;
+    fn insert<Root: Table>(&self, value: &Root) -> Result<Id<Root>, TransformError>;
+
+    /// Insert all `values` into the database within a single transaction,
+    /// reusing the same prepared statement for each insertion.
+    ///
+    /// If any insertion fails, none of the `values` are inserted.
+    fn insert_many<'a, Root, I>(&self, values: I) -> Result<Vec<Id<Root>>, TransformError>
+    where
+        Root: Table,
+        I: IntoIterator<Item = &'a Root>;
+
+    /// Insert `value` into the database, replacing any existing item
+    /// with the same [`Table::key`].
+    ///
+    /// Fails with [`TransformError::MissingKey`] if `Root` does not declare a key.
+    fn upsert<Root: Table>(&self, value: &Root) -> Result<Id<Root>, TransformError>;
 
     /// Retrieve all items matching `transform`.
     fn get<T: Transform>(
@@ -44,28 +494,406 @@ pub trait Database {
         transform: &T,
     ) -> Result<Vec<<<T as Transform>::Output as FromRow>::Output>, TransformError>;
 
+    /// Fold all items matching `transform` into an accumulator using `f`.
+    ///
+    /// Unlike [`Database::get`], items are retrieved and deserialized one at a time,
+    /// so memory usage does not grow with the number of matching items. Iteration
+    /// stops at the first item which fails to deserialize, or for which `f` fails.
+    fn try_fold<T, B, E, F>(&self, transform: &T, init: B, f: F) -> Result<B, E>
+    where
+        T: Transform,
+        E: From<TransformError>,
+        F: FnMut(B, <<T as Transform>::Output as FromRow>::Output) -> Result<B, E>;
+
+    /// Retrieve the first item matching `transform`, if any.
+    fn first<T: Transform>(
+        &self,
+        transform: &T,
+    ) -> Result<Option<<<T as Transform>::Output as FromRow>::Output>, TransformError>;
+
+    /// Retrieve the only item matching `transform`, if any.
+    ///
+    /// Fails with [`TransformError::MultipleResults`] if more than one item matches.
+    fn optional<T: Transform>(
+        &self,
+        transform: &T,
+    ) -> Result<Option<<<T as Transform>::Output as FromRow>::Output>, TransformError>;
+
+    /// Retrieve the only item matching `transform`.
+    ///
+    /// Fails with [`TransformError::NotFound`] if no items match, or
+    /// [`TransformError::MultipleResults`] if more than one item matches.
+    fn one<T: Transform>(
+        &self,
+        transform: &T,
+    ) -> Result<<<T as Transform>::Output as FromRow>::Output, TransformError>;
+
+    /// Retrieve a page of at most `size` items matching `transform`, following
+    /// the item described by `after`, or from the beginning if it is `None`.
+    ///
+    /// Returns the items along with a [`Cursor`] pointing to the last of them,
+    /// for retrieving the next page, or `None` if there are no more items.
+    ///
+    /// Pages follow the [ordering](Transform::order_by) of `transform`, with ties
+    /// broken by insertion order in the same direction. All sort keys must share
+    /// the same direction, and `null` values are placed according to their
+    /// [`Nulls`](crate::order::Nulls) placement.
+    /// Any [limits](Transform::limit) of `transform` are ignored in favour of `size`.
+    ///
+    /// Fails with [`TransformError::UnsupportedDistinct`] for distinct selections,
+    /// whose rows cannot be identified for placing the cursor.
+    #[allow(clippy::type_complexity)]
+    fn get_page<T: Transform>(
+        &self,
+        transform: &T,
+        after: Option<Cursor>,
+        size: usize,
+    ) -> Result<
+        (
+            Vec<<<T as Transform>::Output as FromRow>::Output>,
+            Option<Cursor>,
+        ),
+        TransformError,
+    >;
+
+    /// Count the number of items matching `transform`, without retrieving them.
+    fn count<T: Transform>(&self, transform: &T) -> Result<usize, TransformError>;
+
+    /// For each of the `facets`, count the number of items matching `filter`
+    /// which contain each distinct value of the facet within their array elements.
+    ///
+    /// Returns the counts of each facet in the order the facets were given,
+    /// with the most common values first. Elements without a value are ignored.
+    ///
+    /// ```rust
+    /// # use rejis::{Table, Database, Q};
+    /// # use rejis_test_data::{User, user_database};
+    /// # let db = user_database();
+    /// let pet_names = User::query().pets.facet(|pet| pet.name.clone());
+    ///
+    /// let counts = db.facets(&Q! { User.age > 30 }, &[pet_names]).unwrap();
+    /// assert_eq!(counts[0][0], (serde_json::json!("Jimmy"), 2));
+    /// ```
+    fn facets<T: Transform>(
+        &self,
+        filter: &T,
+        facets: &[Facet<T::Root>],
+    ) -> Result<Vec<Vec<(serde_json::Value, usize)>>, TransformError>;
+
+    /// Group the items matching `filter` by the value at the `key` path, and
+    /// compute `aggregate` over each group, without retrieving the items.
+    ///
+    /// Groups are ordered by their key, and items without a value at the `key`
+    /// path are left out. Only filters producing `Root` objects can be grouped,
+    /// since the key and aggregate describe paths within them.
+    ///
+    /// ```rust
+    /// # use rejis::{Table, Database, Q, aggregate::Count};
+    /// # use rejis_test_data::{User, user_database};
+    /// # let db = user_database();
+    /// let families = db
+    ///     .group_by(
+    ///         &Q! { User.age > 30 },
+    ///         &User::query().last_name,
+    ///         Count(&User::query()),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     families,
+    ///     vec![
+    ///         (String::from("Anderson"), 1),
+    ///         (String::from("LaFleur"), 1),
+    ///         (String::from("Smith"), 2),
+    ///     ]
+    /// );
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn group_by<T, Root, Field, A>(
+        &self,
+        filter: &T,
+        key: &Query<Field, Root>,
+        aggregate: A,
+    ) -> Result<Vec<(Inner<Field, Root>, A::Output)>, TransformError>
+    where
+        T: Transform<Root = Root, Output = Document<Root>>,
+        Root: Table,
+        Field: Queryable<Root>,
+        A: Aggregate<Root = Root>;
+
+    /// Compute `aggregate` over all items matching `filter`, without retrieving them.
+    ///
+    /// Only filters producing `Root` objects can be aggregated, since the
+    /// aggregate describes a path within them.
+    ///
+    /// ```rust
+    /// # use rejis::{Table, Database, Q, aggregate::Avg};
+    /// # use rejis_test_data::{User, user_database};
+    /// # let db = user_database();
+    /// let average_age = db
+    ///     .aggregate(&Q! { User.last_name == "Smith" }, Avg(&User::query().age))
+    ///     .unwrap();
+    /// assert_eq!(average_age, Some(33.5));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use rejis::{Table, Database, Q, aggregate::Avg, transform::Transform};
+    /// # use rejis_test_data::{User, user_database};
+    /// # let db = user_database();
+    /// let ages = Q! { User.last_name == "Smith" }.map(&User::query().age);
+    /// db.aggregate(&ages, Avg(&User::query().age)).unwrap();
+    /// ```
+    fn aggregate<T, Root, A>(&self, filter: &T, aggregate: A) -> Result<A::Output, TransformError>
+    where
+        T: Transform<Root = Root, Output = Document<Root>>,
+        Root: Table,
+        A: Aggregate<Root = Root>;
+
+    /// Check whether any items match `transform`, without retrieving them.
+    fn exists<T: Transform>(&self, transform: &T) -> Result<bool, TransformError>;
+
+    /// Describe how sqlite would evaluate the statement retrieving the items
+    /// matching `transform`, without executing it.
+    ///
+    /// Useful for checking whether a filter makes use of an index, or has to
+    /// scan the whole table.
+    ///
+    /// ```rust
+    /// # use rejis::{Table, Database, Q};
+    /// # use rejis_test_data::{User, user_database};
+    /// # let db = user_database();
+    /// let filter = Q! { User.last_name == "Smith" };
+    /// let plan = db.explain(&filter).unwrap();
+    /// assert_eq!(plan[0].detail, "SCAN user");
+    ///
+    /// db.create_index(&Q! { User.last_name }).unwrap();
+    /// let plan = db.explain(&filter).unwrap();
+    /// assert_eq!(plan[0].detail, "SEARCH user USING INDEX user$.last_name (<expr>=?)");
+    /// ```
+    fn explain<T: Transform>(&self, transform: &T) -> Result<Vec<Step>, TransformError>;
+
+    /// Retrieve all items matching `transform`, along with the [`Id`]
+    /// of the `Root` object each item was retrieved from.
+    ///
+    /// Fails with [`TransformError::UnsupportedDistinct`] for distinct selections,
+    /// whose rows are not retrieved from any single object.
+    #[allow(clippy::type_complexity)]
+    fn get_with_ids<T: Transform>(
+        &self,
+        transform: &T,
+    ) -> Result<
+        Vec<(
+            Id<<T as Transform>::Root>,
+            <<T as Transform>::Output as FromRow>::Output,
+        )>,
+        TransformError,
+    >;
+
+    /// Retrieve the item identified by `id`, if it exists.
+    fn get_by_id<Root: Table>(&self, id: Id<Root>) -> Result<Option<Root>, TransformError>;
+
+    /// Replace the item identified by `id` with `value`.
+    ///
+    /// Returns the number of replaced items.
+    fn replace_by_id<Root: Table>(
+        &self,
+        id: Id<Root>,
+        value: &Root,
+    ) -> Result<usize, TransformError>;
+
+    /// Delete the item identified by `id`.
+    ///
+    /// Returns the number of deleted items.
+    fn delete_by_id<Root: Table>(&self, id: Id<Root>) -> Result<usize, TransformError>;
+
+    /// Write back the upgraded form of all items matching `filter` which were
+    /// stored as a previous version of their structure, see [`Table::upgrades`].
+    ///
+    /// Items are otherwise only upgraded as they are read, leaving their stored
+    /// form untouched, so filters keep comparing against the previous structure.
+    ///
+    /// Returns the number of upgraded items.
+    fn upgrade<T: Transform>(&self, filter: &T) -> Result<usize, TransformError>;
+
+    /// Write back the upgraded form of all items of `Root` which were stored
+    /// as a previous version of their structure, see [`Database::upgrade`].
+    ///
+    /// Returns the number of upgraded items.
+    fn upgrade_all<Root: Table>(&self) -> Result<usize, TransformError>;
+
     /// Delete all items matching `filter`.
     fn delete<T: Transform>(&self, filter: &T) -> Result<usize, TransformError>;
+
+    /// Replace all items matching `filter` with `value`.
+    ///
+    /// Returns the number of replaced items.
+    fn replace<T: Transform>(
+        &self,
+        filter: &T,
+        value: &<T as Transform>::Root,
+    ) -> Result<usize, TransformError>;
+
+    /// Apply `f` to the `target` field of all items matching `filter`,
+    /// leaving the rest of each item untouched.
+    ///
+    /// Items stored as a previous version of their structure are upgraded
+    /// first, so `target` refers to the current structure.
+    ///
+    /// Returns the number of modified items.
+    fn modify<T, Field, F>(
+        &self,
+        filter: &T,
+        target: &Query<Field, <T as Transform>::Root>,
+        f: F,
+    ) -> Result<usize, TransformError>
+    where
+        T: Transform,
+        Field: Queryable<<T as Transform>::Root>,
+        F: FnMut(Field) -> Field;
+
+    /// Apply `update` to all items matching `filter`, without
+    /// retrieving them from the database.
+    ///
+    /// Runs as a single statement, unless `Root` declares any
+    /// [upgrades](Table::upgrades), in which case items stored as a previous
+    /// version of their structure are upgraded first, so `update` applies
+    /// to the current structure.
+    ///
+    /// Returns the number of updated items.
+    fn update<T, U>(&self, filter: &T, update: &U) -> Result<usize, TransformError>
+    where
+        T: Transform,
+        U: Update<Root = <T as Transform>::Root>;
 }
 
-impl Database for Connection {
+impl<C: AsConnection> Database for C {
     fn init<Root: Table>(&self) -> Result<usize, rusqlite::Error> {
-        self.execute(
+        let table = Root::TABLE_NAME;
+        let connection = self.connection();
+
+        check_generated::<Root>()?;
+
+        with_savepoint(connection, || {
+            let created = connection.execute(
+                &format!(
+                    "create table if not exists {table} (value text not null, version integer not null default 0{columns}) strict;",
+                    columns = generated_columns::<Root>()
+                ),
+                (),
+            )?;
+
+            // Tables created before versioning was introduced lack the version column.
+            let versioned: bool = connection.query_row(
+                &format!("select exists(select 1 from pragma_table_info('{table}') where name = 'version')"),
+                (),
+                |row| row.get(0),
+            )?;
+
+            if !versioned {
+                connection.execute(
+                    &format!("alter table {table} add column version integer not null default 0"),
+                    (),
+                )?;
+            }
+
+            // Migrations might affect the key, so they must be applied before indexing it.
+            migration::migrate::<Root>(connection)?;
+
+            // Hidden columns, such as generated ones, are only listed by table_xinfo.
+            let mut columns =
+                connection.prepare(&format!("select name from pragma_table_xinfo('{table}')"))?;
+            let columns = columns
+                .query_map((), |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if Root::generated()
+                .iter()
+                .any(|path| !columns.contains(&path.identifier()))
+            {
+                rebuild_table::<Root>(connection)?;
+            }
+
+            if let Some(key) = Root::key() {
+                connection.execute(
+                    &format!(
+                        "create unique index if not exists {table}_key on {table}({expression});",
+                        expression = table::expression::<Root>(&key, None)
+                    ),
+                    (),
+                )?;
+            }
+
+            for path in Root::indices() {
+                create_index::<Root>(connection, &path)?;
+            }
+
+            Ok(created)
+        })
+    }
+
+    fn create_index<Field, Root>(&self, query: &Query<Field, Root>) -> Result<(), TransformError>
+    where
+        Field: Queryable<Root>,
+        Root: Table,
+    {
+        Ok(create_index::<Root>(self.connection(), query.path())?)
+    }
+
+    fn transaction<R, E, F>(&self, f: F) -> Result<R, E>
+    where
+        E: From<rusqlite::Error>,
+        F: FnOnce(&Self) -> Result<R, E>,
+    {
+        with_savepoint(self.connection(), || f(self))
+    }
+
+    fn insert<Root: Table>(&self, value: &Root) -> Result<Id<Root>, TransformError> {
+        Ok(self.connection().query_row(
             &format!(
-                "create table if not exists {table} (value text not null) strict;",
+                "insert into {table}(value, version) values(json(?1), ?2) returning rowid",
                 table = Root::TABLE_NAME
             ),
-            (),
-        )
+            (serde_json::to_string(value)?, migration::version::<Root>()),
+            |row| row.get(0),
+        )?)
     }
 
-    fn insert<Root: Table>(&self, value: &Root) -> Result<usize, TransformError> {
-        Ok(self.execute(
-            &format!(
-                "insert into {table}(value) values(json(?1))",
+    fn insert_many<'a, Root, I>(&self, values: I) -> Result<Vec<Id<Root>>, TransformError>
+    where
+        Root: Table,
+        I: IntoIterator<Item = &'a Root>,
+    {
+        with_savepoint(self.connection(), || {
+            let mut stmt = self.connection().prepare(&format!(
+                "insert into {table}(value, version) values(json(?1), ?2) returning rowid",
                 table = Root::TABLE_NAME
+            ))?;
+            let version = migration::version::<Root>();
+
+            values
+                .into_iter()
+                .map(|value| {
+                    Ok(stmt.query_row((serde_json::to_string(value)?, version), |row| row.get(0))?)
+                })
+                .collect()
+        })
+    }
+
+    fn upsert<Root: Table>(&self, value: &Root) -> Result<Id<Root>, TransformError> {
+        let table = Root::TABLE_NAME;
+        let key = Root::key().ok_or(TransformError::MissingKey(table))?;
+
+        Ok(self.connection().query_row(
+            &format!(
+                "
+insert into {table}(value, version) values(json(?1), ?2)
+on conflict({key})
+do update set value = excluded.value, version = excluded.version
+returning rowid",
+                key = table::expression::<Root>(&key, None)
             ),
-            (serde_json::to_string(value)?,),
+            (serde_json::to_string(value)?, migration::version::<Root>()),
+            |row| row.get(0),
         )?)
     }
 
@@ -73,44 +901,558 @@ impl Database for Connection {
         &self,
         transform: &T,
     ) -> Result<Vec<<<T as Transform>::Output as FromRow>::Output>, TransformError> {
+        self.try_fold(transform, Vec::new(), |mut objects, object| {
+            objects.push(object);
+            Ok(objects)
+        })
+    }
+
+    fn try_fold<T, B, E, F>(&self, transform: &T, init: B, f: F) -> Result<B, E>
+    where
+        T: Transform,
+        E: From<TransformError>,
+        F: FnMut(B, <<T as Transform>::Output as FromRow>::Output) -> Result<B, E>,
+    {
+        fold_rows(
+            self.connection(),
+            transform,
+            &transform.statement(),
+            transform.limits(),
+            init,
+            f,
+        )
+    }
+
+    fn first<T: Transform>(
+        &self,
+        transform: &T,
+    ) -> Result<Option<<<T as Transform>::Output as FromRow>::Output>, TransformError> {
+        let mut objects = fetch_at_most(self.connection(), transform, 1)?;
+        Ok(objects.pop())
+    }
+
+    fn optional<T: Transform>(
+        &self,
+        transform: &T,
+    ) -> Result<Option<<<T as Transform>::Output as FromRow>::Output>, TransformError> {
+        let mut objects = fetch_at_most(self.connection(), transform, 2)?;
+        if objects.len() > 1 {
+            return Err(TransformError::MultipleResults);
+        }
+
+        Ok(objects.pop())
+    }
+
+    fn one<T: Transform>(
+        &self,
+        transform: &T,
+    ) -> Result<<<T as Transform>::Output as FromRow>::Output, TransformError> {
+        self.optional(transform)?.ok_or(TransformError::NotFound)
+    }
+
+    fn get_page<T: Transform>(
+        &self,
+        transform: &T,
+        after: Option<Cursor>,
+        size: usize,
+    ) -> Result<
+        (
+            Vec<<<T as Transform>::Output as FromRow>::Output>,
+            Option<Cursor>,
+        ),
+        TransformError,
+    > {
+        if transform.is_distinct() {
+            return Err(TransformError::UnsupportedDistinct);
+        }
+
+        let keys = transform.ordering();
+        let direction = cursor::direction(&keys)?;
+
+        if let Some(cursor) = &after {
+            if cursor.keys.len() != keys.len() {
+                return Err(TransformError::InvalidCursor);
+            }
+        }
+
+        let paged = After {
+            inner: transform,
+            keys: &keys,
+            direction,
+            cursor: after.as_ref(),
+        };
+
+        let mut columns = vec![transform.columns()];
+        let mut ordering = Vec::new();
+        for key in &keys {
+            columns.push(format!("json_extract(result.value, '{}')", key.path));
+            ordering.push(key.to_string());
+        }
+        columns.push(String::from("result.rowid"));
+        ordering.push(format!("result.rowid {direction}"));
+
+        let sql = sql_query_builder(
+            <T as Transform>::Root::TABLE_NAME,
+            &paged,
+            &format!(
+                "select {columns} from result\norder by {ordering}\nlimit ?",
+                columns = columns.join(", "),
+                ordering = ordering.join(", ")
+            ),
+        )
+        .unwrap();
+
+        let mut stmt = self.connection().prepare(&sql)?;
+        let mut index = 1;
+        paged.bind(&mut stmt, &mut index)?;
+        stmt.raw_bind_parameter(index, size)?;
+
+        let rowid_column = stmt.column_count() - 1;
+        let keys_column = rowid_column - keys.len();
+
+        let mut objects = Vec::new();
+        let mut last = None;
+        let mut rows = stmt.raw_query();
+        while let Some(result) = rows.next()? {
+            objects.push(transform.extract(result)?);
+            last = Some(Cursor {
+                keys: (keys_column..rowid_column)
+                    .map(|column| Ok(value::to_json(result.get_ref(column)?)))
+                    .collect::<Result<_, rusqlite::Error>>()?,
+                rowid: result.get(rowid_column)?,
+            });
+        }
+
+        if objects.len() < size {
+            last = None;
+        }
+
+        Ok((objects, last))
+    }
+
+    fn aggregate<T, Root, A>(&self, filter: &T, aggregate: A) -> Result<A::Output, TransformError>
+    where
+        T: Transform<Root = Root, Output = Document<Root>>,
+        Root: Table,
+        A: Aggregate<Root = Root>,
+    {
+        let limits = filter.limits();
+
+        let sql = sql_query_builder(
+            <T as Transform>::Root::TABLE_NAME,
+            filter,
+            &format!(
+                "select {expression} from (
+    select result.value from result{clauses}
+)",
+                expression = aggregate.expression("value"),
+                clauses = clauses(filter, &limits)
+            ),
+        )
+        .unwrap();
+
+        let mut stmt = self.connection().prepare(&sql)?;
+        let mut index = 1;
+        filter.bind(&mut stmt, &mut index)?;
+        limits.bind(&mut stmt, &mut index)?;
+
+        let mut rows = stmt.raw_query();
+        let row = rows.next()?.ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+        aggregate.extract(row, 0)
+    }
+
+    fn group_by<T, Root, Field, A>(
+        &self,
+        filter: &T,
+        key: &Query<Field, Root>,
+        aggregate: A,
+    ) -> Result<Vec<(Inner<Field, Root>, A::Output)>, TransformError>
+    where
+        T: Transform<Root = Root, Output = Document<Root>>,
+        Root: Table,
+        Field: Queryable<Root>,
+        A: Aggregate<Root = Root>,
+    {
+        let limits = filter.limits();
+        let path = key.path();
+
+        let sql = sql_query_builder(
+            <T as Transform>::Root::TABLE_NAME,
+            filter,
+            &format!(
+                "select {key}, {expression} from (
+    select result.value from result{clauses}
+)
+where json_type(value, '{path}') is not null
+group by json_extract(value, '{path}')
+order by json_extract(value, '{path}')",
+                key = path.json("value"),
+                expression = aggregate.expression("value"),
+                clauses = clauses(filter, &limits)
+            ),
+        )
+        .unwrap();
+
+        let mut stmt = self.connection().prepare(&sql)?;
+        let mut index = 1;
+        filter.bind(&mut stmt, &mut index)?;
+        limits.bind(&mut stmt, &mut index)?;
+
+        let mut groups = Vec::new();
+        let mut rows = stmt.raw_query();
+        while let Some(row) = rows.next()? {
+            let key: String = row.get(0)?;
+            let key = serde_json::from_str(&key)?;
+            groups.push((key, aggregate.extract(row, 1)?));
+        }
+
+        Ok(groups)
+    }
+
+    fn facets<T: Transform>(
+        &self,
+        filter: &T,
+        facets: &[Facet<T::Root>],
+    ) -> Result<Vec<Vec<(serde_json::Value, usize)>>, TransformError> {
+        let limits = filter.limits();
+        let source = format!(
+            "select result.rowid, result.value from result{clauses}",
+            clauses = clauses(filter, &limits)
+        );
+
+        let mut counts = Vec::with_capacity(facets.len());
+        for facet in facets {
+            let sql = sql_query_builder(
+                <T as Transform>::Root::TABLE_NAME,
+                filter,
+                &facet.statement(&source),
+            )
+            .unwrap();
+
+            let mut stmt = self.connection().prepare(&sql)?;
+            let mut index = 1;
+            filter.bind(&mut stmt, &mut index)?;
+            limits.bind(&mut stmt, &mut index)?;
+
+            let mut values = Vec::new();
+            let mut rows = stmt.raw_query();
+            while let Some(row) = rows.next()? {
+                let value: String = row.get(0)?;
+                values.push((serde_json::from_str(&value)?, row.get(1)?));
+            }
+
+            counts.push(values);
+        }
+
+        Ok(counts)
+    }
+
+    fn count<T: Transform>(&self, transform: &T) -> Result<usize, TransformError> {
+        query_scalar(self.connection(), transform, |statement| {
+            format!("select count(*) from ({statement})")
+        })
+    }
+
+    fn exists<T: Transform>(&self, transform: &T) -> Result<bool, TransformError> {
+        query_scalar(self.connection(), transform, |statement| {
+            format!("select exists({statement})")
+        })
+    }
+
+    fn explain<T: Transform>(&self, transform: &T) -> Result<Vec<Step>, TransformError> {
+        let limits = transform.limits();
+
         let sql = sql_query_builder(
             <T as Transform>::Root::TABLE_NAME,
             transform,
-            &transform.statement(),
+            &format!(
+                "{statement}{clauses}",
+                statement = transform.statement(),
+                clauses = clauses(transform, &limits)
+            ),
         )
         .unwrap();
 
-        let mut stmt = self.prepare(&sql)?;
-        transform.bind(&mut stmt, &mut 1)?;
+        let mut stmt = self
+            .connection()
+            .prepare(&format!("explain query plan {sql}"))?;
+        let mut index = 1;
+        transform.bind(&mut stmt, &mut index)?;
+        limits.bind(&mut stmt, &mut index)?;
+
+        let mut steps = Vec::new();
+        let mut rows = stmt.raw_query();
+        while let Some(row) = rows.next()? {
+            steps.push(Step::from_row(row)?);
+        }
+
+        Ok(steps)
+    }
+
+    fn get_with_ids<T: Transform>(
+        &self,
+        transform: &T,
+    ) -> Result<
+        Vec<(
+            Id<<T as Transform>::Root>,
+            <<T as Transform>::Output as FromRow>::Output,
+        )>,
+        TransformError,
+    > {
+        if transform.is_distinct() {
+            return Err(TransformError::UnsupportedDistinct);
+        }
+
+        let limits = transform.limits();
+
+        let sql = sql_query_builder(
+            <T as Transform>::Root::TABLE_NAME,
+            transform,
+            &format!(
+                "select {columns}, result.rowid from result{clauses}",
+                columns = transform.columns(),
+                clauses = clauses(transform, &limits)
+            ),
+        )
+        .unwrap();
+
+        let mut stmt = self.connection().prepare(&sql)?;
+        let mut index = 1;
+        transform.bind(&mut stmt, &mut index)?;
+        limits.bind(&mut stmt, &mut index)?;
+
+        let id_column = stmt.column_count() - 1;
 
         let mut objects = Vec::new();
         let mut rows = stmt.raw_query();
         while let Some(result) = rows.next()? {
-            objects.push(transform.extract(result).unwrap());
+            objects.push((result.get(id_column)?, transform.extract(result)?));
         }
 
         Ok(objects)
     }
 
+    fn get_by_id<Root: Table>(&self, id: Id<Root>) -> Result<Option<Root>, TransformError> {
+        let value: Option<(String, usize)> = self
+            .connection()
+            .query_row(
+                &format!(
+                    "select value, version from {table} where rowid = ?1",
+                    table = Root::TABLE_NAME
+                ),
+                (id,),
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        value
+            .map(|(value, version)| migration::upgrade(version, &value))
+            .transpose()
+    }
+
+    fn replace_by_id<Root: Table>(
+        &self,
+        id: Id<Root>,
+        value: &Root,
+    ) -> Result<usize, TransformError> {
+        Ok(self.connection().execute(
+            &format!(
+                "update {table} set value = json(?1), version = ?2 where rowid = ?3",
+                table = Root::TABLE_NAME
+            ),
+            (
+                serde_json::to_string(value)?,
+                migration::version::<Root>(),
+                id,
+            ),
+        )?)
+    }
+
+    fn delete_by_id<Root: Table>(&self, id: Id<Root>) -> Result<usize, TransformError> {
+        Ok(self.connection().execute(
+            &format!(
+                "delete from {table} where rowid = ?1",
+                table = Root::TABLE_NAME
+            ),
+            (id,),
+        )?)
+    }
+
+    fn upgrade<T: Transform>(&self, filter: &T) -> Result<usize, TransformError> {
+        let connection = self.connection();
+        with_savepoint(connection, || upgrade_rows(connection, filter))
+    }
+
+    fn upgrade_all<Root: Table>(&self) -> Result<usize, TransformError> {
+        let connection = self.connection();
+        with_savepoint(connection, || {
+            let mut stmt = connection.prepare(&format!(
+                "select rowid, version, value from {table} where version < ?1",
+                table = Root::TABLE_NAME
+            ))?;
+            stmt.raw_bind_parameter(1, migration::version::<Root>())?;
+
+            write_upgraded::<Root>(connection, stmt)
+        })
+    }
+
     fn delete<T: Transform>(&self, filter: &T) -> Result<usize, TransformError> {
         let table = <T as Transform>::Root::TABLE_NAME;
+        let limits = filter.limits();
+
+        let sql = sql_query_builder(
+            table,
+            filter,
+            &format!(
+                "\ndelete from {table}{selector}",
+                selector = matching_rows(table, filter, &limits)
+            ),
+        )
+        .unwrap();
+
+        let mut stmt = self.connection().prepare(&sql)?;
+        let mut index = 1;
+        filter.bind(&mut stmt, &mut index)?;
+        limits.bind(&mut stmt, &mut index)?;
+        Ok(stmt.raw_execute()?)
+    }
+
+    fn replace<T: Transform>(
+        &self,
+        filter: &T,
+        value: &<T as Transform>::Root,
+    ) -> Result<usize, TransformError> {
+        let table = <T as Transform>::Root::TABLE_NAME;
+        let limits = filter.limits();
 
         let sql = sql_query_builder(
             table,
             filter,
             &format!(
                 "
-delete from {table}
-where exists (
-    select * from result
-    where result.rowid = {table}.rowid
-)"
+update {table}
+set value = json(?), version = ?{selector}",
+                selector = matching_rows(table, filter, &limits)
             ),
         )
         .unwrap();
 
-        let mut stmt = self.prepare(&sql)?;
-        filter.bind(&mut stmt, &mut 1)?;
+        let mut stmt = self.connection().prepare(&sql)?;
+        let mut index = 1;
+        filter.bind(&mut stmt, &mut index)?;
+        stmt.raw_bind_parameter(index, serde_json::to_string(value)?)?;
+        stmt.raw_bind_parameter(index + 1, migration::version::<T::Root>())?;
+        index += 2;
+        limits.bind(&mut stmt, &mut index)?;
         Ok(stmt.raw_execute()?)
     }
+
+    fn modify<T, Field, F>(
+        &self,
+        filter: &T,
+        target: &Query<Field, <T as Transform>::Root>,
+        mut f: F,
+    ) -> Result<usize, TransformError>
+    where
+        T: Transform,
+        Field: Queryable<<T as Transform>::Root>,
+        F: FnMut(Field) -> Field,
+    {
+        // Modify all targets atomically, so a failure part-way through
+        // does not leave only some of them modified.
+        with_savepoint(self.connection(), || {
+            let connection = self.connection();
+            let table = <T as Transform>::Root::TABLE_NAME;
+            let path = target.path();
+            let limits = filter.limits();
+            let version = migration::version::<T::Root>();
+
+            if version > 0 {
+                upgrade_rows(connection, filter)?;
+            }
+
+            let sql = sql_query_builder(
+                table,
+                filter,
+                &format!(
+                    "select result.rowid, {field} from result{clauses}",
+                    field = path.json("result.value"),
+                    clauses = clauses(filter, &limits)
+                ),
+            )
+            .unwrap();
+
+            let mut stmt = connection.prepare(&sql)?;
+            let mut index = 1;
+            filter.bind(&mut stmt, &mut index)?;
+            limits.bind(&mut stmt, &mut index)?;
+
+            let mut targets = Vec::new();
+            let mut rows = stmt.raw_query();
+            while let Some(row) = rows.next()? {
+                let rowid: i64 = row.get(0)?;
+                let field: String = row.get(1)?;
+                targets.push((rowid, serde_json::from_str::<Field>(&field)?));
+            }
+
+            let mut update = connection.prepare(&format!(
+                "update {table} set value = json_set(value, '{path}', json(?1)), version = ?2 where rowid = ?3"
+            ))?;
+
+            let mut modified = 0;
+            for (rowid, field) in targets {
+                modified += update.execute((serde_json::to_string(&f(field))?, version, rowid))?;
+            }
+
+            Ok(modified)
+        })
+    }
+
+    fn update<T, U>(&self, filter: &T, update: &U) -> Result<usize, TransformError>
+    where
+        T: Transform,
+        U: Update<Root = <T as Transform>::Root>,
+    {
+        let connection = self.connection();
+        let table = <T as Transform>::Root::TABLE_NAME;
+        let limits = filter.limits();
+        let version = migration::version::<T::Root>();
+
+        let mut expression = String::new();
+        update.expression("value", &mut expression).unwrap();
+
+        let sql = sql_query_builder(
+            table,
+            filter,
+            &format!(
+                "
+update {table}
+set value = {expression}, version = ?{selector}",
+                selector = matching_rows(table, filter, &limits)
+            ),
+        )
+        .unwrap();
+
+        let execute = || -> Result<usize, TransformError> {
+            let mut stmt = connection.prepare(&sql)?;
+            let mut index = 1;
+            filter.bind(&mut stmt, &mut index)?;
+            update.bind(&mut stmt, &mut index)?;
+            stmt.raw_bind_parameter(index, version)?;
+            index += 1;
+            limits.bind(&mut stmt, &mut index)?;
+            Ok(stmt.raw_execute()?)
+        };
+
+        // Outdated items are upgraded first, which only takes place
+        // alongside the update if there are any upgrades at all.
+        if version > 0 {
+            with_savepoint(connection, || {
+                upgrade_rows(connection, filter)?;
+                execute()
+            })
+        } else {
+            execute()
+        }
+    }
 }
@@ -0,0 +1,27 @@
+//! Structures describing how sqlite evaluates queries.
+use rusqlite::Row;
+
+/// Single step of the plan sqlite uses for evaluating a query, as returned
+/// by [`Database::explain`](crate::Database::explain).
+///
+/// Steps form a tree, in which each step is nested beneath the step whose
+/// `id` matches its `parent`, or at the top if its `parent` is zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub id: i64,
+    pub parent: i64,
+    /// Description of the step, such as `SCAN user`, or
+    /// `SEARCH user USING INDEX user$.last_name (<expr>=?)`.
+    pub detail: String,
+}
+
+impl Step {
+    /// Construct a step from a row produced by `explain query plan`.
+    pub(crate) fn from_row(row: &Row) -> Result<Self, rusqlite::Error> {
+        Ok(Step {
+            id: row.get(0)?,
+            parent: row.get(1)?,
+            detail: row.get(3)?,
+        })
+    }
+}
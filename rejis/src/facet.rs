@@ -0,0 +1,73 @@
+//! Structures used for counting the values found within arrays of matched objects.
+use crate::{Path, Table};
+use std::marker::PhantomData;
+
+/// Describes a path into the elements of an array field of a `Root` object,
+/// for which [`Database::facets`](crate::Database::facets) counts the
+/// number of objects containing each distinct value.
+///
+/// Constructed using [`Query::facet`](crate::Query::facet).
+pub struct Facet<Root>
+where
+    Root: Table,
+{
+    pub(crate) outer_path: Path,
+    pub(crate) inner_path: Path,
+    pub(crate) _data: PhantomData<fn() -> Root>,
+}
+
+impl<Root> Clone for Facet<Root>
+where
+    Root: Table,
+{
+    fn clone(&self) -> Self {
+        Facet {
+            outer_path: self.outer_path.clone(),
+            inner_path: self.inner_path.clone(),
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<Root> std::fmt::Debug for Facet<Root>
+where
+    Root: Table,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Facet")
+            .field("outer_path", &self.outer_path)
+            .field("inner_path", &self.inner_path)
+            .finish()
+    }
+}
+
+impl<Root> Facet<Root>
+where
+    Root: Table,
+{
+    /// Statement counting the objects selected by `source` containing each
+    /// distinct value of this facet, most common values first.
+    pub(crate) fn statement(&self, source: &str) -> String {
+        // Values are read from the matched object through the full path of each
+        // element, since json_each produces elements which are not objects as
+        // plain sql values, rather than as json.
+        let inner_path = self.inner_path.to_string();
+        let path = format!(
+            "(json_each.fullkey || '{suffix}')",
+            suffix = inner_path.trim_start_matches('$')
+        );
+        let value = format!("json_extract(matched.value, {path})");
+        let json = format!("coalesce(matched.value -> {path}, 'null')");
+
+        format!(
+            "select {json}, count(distinct matched.rowid)
+from (
+    {source}
+) as matched, json_each(matched.value, '{outer_path}')
+where {value} is not null
+group by {value}
+order by 2 desc, {value}",
+            outer_path = self.outer_path
+        )
+    }
+}
@@ -1,6 +1,7 @@
 //! Structures used for applying filters to queries.
 use crate::{
-    transform::{FromRow, Transform},
+    table,
+    transform::{Document, Transform},
     Query, QueryConstructor, Queryable, Table,
 };
 use rusqlite::{Statement, ToSql};
@@ -64,12 +65,11 @@ impl<Field, Root> Transform for Comparison<Field, Root>
 where
     Field: Queryable<Root>,
     Root: Table,
-    (Root,): FromRow,
     <Field::QueryType as QueryConstructor<Root>>::Inner: ToSql,
 {
     type Root = Root;
     type Field = Field;
-    type Output = (Root,);
+    type Output = Document<Root>;
 
     fn bind(
         &self,
@@ -85,18 +85,14 @@ where
         write!(
             f,
             ",\n    {name} as (
-        select root.rowid, root.value
+        select root.rowid, root.value, root.version
         from root
-        where json_extract(root.value, '{path}') {operator} ?
+        where {expression} {operator} ?
     )",
-            path = self.query.path(),
+            expression = table::expression::<Root>(self.query.path(), Some("root")),
             operator = self.operator
         )
     }
-
-    fn statement(&self) -> String {
-        String::from("select result.value from result")
-    }
 }
 
 #[derive(Clone)]
@@ -115,14 +111,13 @@ where
 impl<Root, Field, A, B> Transform for And<A, B>
 where
     Root: Table,
-    (Root,): FromRow,
     Field: Queryable<Root>,
     A: Transform<Root = Root, Field = Field>,
     B: Transform<Root = Root, Field = Field>,
 {
     type Root = Root;
     type Field = Field;
-    type Output = (Root,);
+    type Output = Document<Root>;
 
     fn bind(
         &self,
@@ -141,7 +136,7 @@ where
         write!(
             f,
             ",\n    {name} as (
-        select {name}_a.rowid, {name}_a.value
+        select {name}_a.rowid, {name}_a.value, {name}_a.version
         from {name}_a
         inner join {name}_b
         on {name}_a.rowid = {name}_b.rowid
@@ -166,14 +161,13 @@ where
 impl<Root, Field, A, B> Transform for Or<A, B>
 where
     Root: Table,
-    (Root,): FromRow,
     Field: Queryable<Root>,
     A: Transform<Root = Root, Field = Field>,
     B: Transform<Root = Root, Field = Field>,
 {
     type Root = Root;
     type Field = Field;
-    type Output = (Root,);
+    type Output = Document<Root>;
 
     fn bind(
         &self,
@@ -237,12 +231,11 @@ where
     Field: Queryable<Root>,
     InnerField: Queryable<Root>,
     Root: Table,
-    (Root,): FromRow,
     <InnerField::QueryType as QueryConstructor<Root>>::Inner: ToSql,
 {
     type Root = Root;
     type Field = InnerField;
-    type Output = (Root,);
+    type Output = Document<Root>;
 
     fn bind(
         &self,
@@ -259,8 +252,8 @@ where
         write!(
             f,
             ",\n    {name} as (
-        select distinct rowid, value from (
-            select root.rowid, root.value
+        select distinct rowid, value, version from (
+            select root.rowid, root.value, root.version
             from root, json_each(root.value, '{outer_path}')
             where json_extract(json_each.value, '{inner_path}') {operator} ?
         )
@@ -0,0 +1,89 @@
+use rusqlite::{
+    types::{FromSql, FromSqlResult, ToSqlOutput, ValueRef},
+    ToSql,
+};
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Formatter},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+/// Typed identifier of an object stored in the table of `Root`.
+///
+/// Wraps the sqlite `rowid` of the row containing the object, and is returned
+/// when inserting objects using [`Database::insert`](crate::Database::insert).
+pub struct Id<Root> {
+    rowid: i64,
+    _data: PhantomData<fn() -> Root>,
+}
+
+impl<Root> Id<Root> {
+    /// Construct an `Id` from a raw sqlite `rowid`.
+    pub fn new(rowid: i64) -> Self {
+        Id {
+            rowid,
+            _data: PhantomData,
+        }
+    }
+
+    /// Raw sqlite `rowid` of the identified row.
+    pub fn rowid(&self) -> i64 {
+        self.rowid
+    }
+}
+
+// The following traits are implemented manually, since deriving them
+// would needlessly require `Root` to implement them as well.
+
+impl<Root> Clone for Id<Root> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Root> Copy for Id<Root> {}
+
+impl<Root> PartialEq for Id<Root> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rowid == other.rowid
+    }
+}
+
+impl<Root> Eq for Id<Root> {}
+
+impl<Root> PartialOrd for Id<Root> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Root> Ord for Id<Root> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rowid.cmp(&other.rowid)
+    }
+}
+
+impl<Root> Hash for Id<Root> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rowid.hash(state)
+    }
+}
+
+impl<Root> Debug for Id<Root> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Id").field(&self.rowid).finish()
+    }
+}
+
+impl<Root> ToSql for Id<Root> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        self.rowid.to_sql()
+    }
+}
+
+impl<Root> FromSql for Id<Root> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        i64::column_result(value).map(Id::new)
+    }
+}
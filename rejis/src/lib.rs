@@ -259,36 +259,120 @@
 //! assert_eq!(deleted_rows, 1);
 //! ```
 //!
-//! # Roadmap
-//! * **Updating/replacing entire entries**
-//!   ```rust,ignore
-//!   // Replace John Smith with Jane Doe
-//!   Q! {
-//!     (User.first_name == "John") && (User.last_name == "Smith")
-//!   }.replace(&conn, User {
-//!     first_name: "Jane".to_string(),
-//!     last_name: "Doe".to_string(),
-//!   })
-//!   ```
+//! **Replacing**
+//! ```rust
+//! # use rejis::{Database, Queryable, Table};
+//! # use serde::{Serialize, Deserialize};
+//! # use rusqlite::Connection;
+//! #
+//! # #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+//! # struct User {
+//! #   first_name: String,
+//! #   last_name: String,
+//! #   pets: Vec<String>,
+//! #   age: u8,
+//! # }
+//! #
+//! # let conn = Connection::open_in_memory().unwrap();
+//! # conn.init::<User>().unwrap();
+//! #
+//! # conn.insert(&User {
+//! #   first_name: String::from("Sabrina"),
+//! #   last_name: String::from("Spellman"),
+//! #   age: 15,
+//! #   pets: vec![
+//! #     String::from("Salem"),
+//! #   ],
+//! # }).unwrap();
+//! #
+//! # use rejis::Q;
+//! // Replace Sabrina Spellman with Jane Doe
+//! let replaced_rows = conn.replace(
+//!   &Q! {
+//!     (User.first_name == "Sabrina") && (User.last_name == "Spellman")
+//!   },
+//!   &User {
+//!     first_name: String::from("Jane"),
+//!     last_name: String::from("Doe"),
+//!     age: 27,
+//!     pets: vec![],
+//!   }
+//! ).unwrap();
+//!
+//! assert_eq!(replaced_rows, 1);
+//! ```
+//!
+//! **Modifying**
+//!
+//! For large objects, replacing the entire entry just to change a single field is wasteful.
+//! Using a filter for selection, a query for targeting, and a closure for manipulation,
+//! only the targeted field is read and rewritten:
+//! ```rust
+//! # use rejis::{Database, Queryable, Table};
+//! # use serde::{Serialize, Deserialize};
+//! # use rusqlite::Connection;
+//! #
+//! # #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+//! # struct User {
+//! #   first_name: String,
+//! #   last_name: String,
+//! #   pets: Vec<String>,
+//! #   age: u8,
+//! # }
+//! #
+//! # let conn = Connection::open_in_memory().unwrap();
+//! # conn.init::<User>().unwrap();
+//! #
+//! # conn.insert(&User {
+//! #   first_name: String::from("Jon"),
+//! #   last_name: String::from("Arbuckle"),
+//! #   age: 29,
+//! #   pets: vec![
+//! #     String::from("Garfield"),
+//! #     String::from("Odie"),
+//! #   ],
+//! # }).unwrap();
+//! #
+//! # use rejis::Q;
+//! // Uppercase all the last names of people called Jon
+//! let modified_rows = conn.modify(
+//!   // Select specific rows
+//!   &Q! { User.first_name == "Jon" },
+//!   // Target the last_name specifically for replacement
+//!   &Q! { User.last_name },
+//!   // Provide a function for how the name should be transformed.
+//!   |last_name| last_name.to_uppercase()
+//! ).unwrap();
+//!
+//! assert_eq!(modified_rows, 1);
+//! ```
+//!
+//! **Indexing**
 //!
+//! Fields which are frequently used for filtering can be indexed, either by marking
+//! them with `#[rejis(index)]` when deriving [`Table`], or explicitly:
+//! ```rust
+//! # use rejis::{Database, Queryable, Table};
+//! # use serde::{Serialize, Deserialize};
+//! # use rusqlite::Connection;
+//! #
+//! # #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+//! # struct User {
+//! #   first_name: String,
+//! #   last_name: String,
+//! #   pets: Vec<String>,
+//! #   age: u8,
+//! # }
+//! #
+//! # let conn = Connection::open_in_memory().unwrap();
+//! # conn.init::<User>().unwrap();
+//! #
+//! # use rejis::Q;
+//! conn.create_index(&Q! { User.last_name }).unwrap();
+//! ```
+//!
+//! # Roadmap
 //! ## Tentative features
-
-//! * **Partial updates**
-//!
-//!   Using a filter for selection, a query for targeting, and a closure for manipulation,
-//!   a nice api could be made for highly selective updates
-//!   ```rust,ignore
-//!   // Uppercase all the last names of people called Jon
-//!   db.modify(
-//!     // Select specific rows
-//!     &Q! { User.first_name == "Jon" },
-//!     // Target the last_name specifically for replacement
-//!     &Q! { User.last_name },
-//!     // Provide a function for how the name should be transformed.
-//!     |last_name| last_name.to_uppercase()
-//!   );
-//!   ```
-//!   Query mapping might be very relevant for the final form of this API.
 //!
 //! * **Two-stage Query application**
 //!
@@ -303,35 +387,53 @@
 //!   statements, presumeably based on the input sql, so if the sql statement itself can be constructed
 //!   once, and then reused, it might still have an impact.
 //!
-//!
-//! * **Expression indices**
-//!   
-//!   Sqlite supports creating an index over an expression within a table,
-//!   which might be useful for improving the performance of commonly used queries.
-//!   If you use `rejis` for storing users for example, it might be useful to be able to create
-//!   an index over `json_extract(value, '$.id')` or `$.name`, if those are used to find users.
-//!
 //! # Shortcomings
 //! * Query paths only allow a single indexing element.
 //!   Reason for this is in the complexity of implementing the SQL CTE and the Q!-macro DSL support for that use case.
 //!
+pub mod aggregate;
+pub mod cursor;
+pub mod explain;
+pub mod facet;
 pub mod filter;
+pub mod limit;
 mod map;
+pub mod migration;
+pub mod open;
+pub mod order;
 pub mod transform;
+pub mod update;
+mod value;
 
 mod table;
 pub use table::Table;
 
+mod id;
+pub use id::Id;
+
 mod database;
-pub use database::Database;
+pub use database::{AsConnection, Database};
+
+mod store;
+pub use store::Store;
+
+pub use open::open;
+
+#[cfg(feature = "async")]
+mod async_database;
+#[cfg(feature = "async")]
+pub use async_database::AsyncDatabase;
 
 #[cfg(feature = "derive")]
 pub use rejis_derive::{Queryable, Table};
 
+// Lints introduced by newer toolchains, which the query module predates.
+#[allow(clippy::empty_docs, clippy::default_constructed_unit_structs)]
 mod query;
 pub use query::*;
 
 #[cfg(feature = "macros")]
 mod macros;
 #[cfg(feature = "macros")]
+#[allow(unused_imports)]
 pub use macros::*;
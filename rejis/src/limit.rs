@@ -0,0 +1,126 @@
+//! Structures used for limiting the number of results produced by queries.
+use crate::{
+    order::SortKey,
+    transform::{FromRow, Transform, TransformError},
+};
+use rusqlite::{Row, Statement};
+
+/// Upper bound on the number of items produced by the final statement of
+/// a [`Transform`], and the number of items to skip before that.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl Limits {
+    /// Clause appended to the final statement for applying these limits.
+    pub(crate) fn clause(&self) -> &'static str {
+        if *self == Limits::default() {
+            ""
+        } else {
+            "\nlimit ? offset ?"
+        }
+    }
+
+    /// Apply `outer` to the items remaining after applying these limits,
+    /// the same way slicing an already sliced list would.
+    pub(crate) fn narrow(&self, outer: &Limits) -> Limits {
+        let remaining = self
+            .limit
+            .map(|limit| limit.saturating_sub(outer.offset.unwrap_or(0)));
+
+        let limit = match (remaining, outer.limit) {
+            (Some(remaining), Some(limit)) => Some(remaining.min(limit)),
+            (remaining, limit) => remaining.or(limit),
+        };
+
+        let offset = match (self.offset, outer.offset) {
+            (None, None) => None,
+            (inner, outer) => Some(inner.unwrap_or(0) + outer.unwrap_or(0)),
+        };
+
+        Limits { limit, offset }
+    }
+
+    /// Bind the parameters of the clause produced by [`Limits::clause`].
+    pub(crate) fn bind(
+        &self,
+        statement: &mut Statement<'_>,
+        index: &mut usize,
+    ) -> Result<(), rusqlite::Error> {
+        if *self == Limits::default() {
+            return Ok(());
+        }
+
+        // Sqlite only supports offsets as part of a limit clause,
+        // where a negative limit means no limit at all.
+        let limit = self.limit.map(|limit| limit as i64).unwrap_or(-1);
+        statement.raw_bind_parameter(*index, limit)?;
+        statement.raw_bind_parameter(*index + 1, self.offset.unwrap_or(0))?;
+        *index += 2;
+        Ok(())
+    }
+}
+
+/// Applies a limit and/or offset to the results of the `Inner` transform.
+///
+/// Repeated limits and offsets narrow the results further, so
+/// `.offset(1).limit(2)` produces the second and third results, while
+/// `.limit(2).offset(1)` only produces the second.
+///
+/// Only the outermost limit and offset are applied. Limits placed
+/// within the operands of filters such as [`And`](crate::filter::And)
+/// are ignored.
+pub struct Limit<Inner>
+where
+    Inner: Transform,
+{
+    pub(crate) inner: Inner,
+    pub(crate) limits: Limits,
+}
+
+impl<Inner> Transform for Limit<Inner>
+where
+    Inner: Transform,
+{
+    type Root = Inner::Root;
+    type Field = Inner::Field;
+    type Output = Inner::Output;
+
+    fn bind(
+        &self,
+        statement: &mut Statement<'_>,
+        index: &mut usize,
+    ) -> Result<(), rusqlite::Error> {
+        self.inner.bind(statement, index)
+    }
+
+    fn cte(&self, name: &str, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        self.inner.cte(name, f)
+    }
+
+    fn columns(&self) -> String {
+        self.inner.columns()
+    }
+
+    fn statement(&self) -> String {
+        self.inner.statement()
+    }
+
+    fn is_distinct(&self) -> bool {
+        self.inner.is_distinct()
+    }
+
+    fn extract(&self, row: &Row) -> Result<<Self::Output as FromRow>::Output, TransformError> {
+        self.inner.extract(row)
+    }
+
+    fn limits(&self) -> Limits {
+        self.inner.limits().narrow(&self.limits)
+    }
+
+    fn ordering(&self) -> Vec<SortKey> {
+        self.inner.ordering()
+    }
+}
@@ -35,6 +35,12 @@ macro_rules! Q {
             Q!($($tail)*),
         )
     };
+    // Terminal path step with no comparison, such as `User.last_name`,
+    // yielding the `Query` itself. Must precede the comparison rules,
+    // since the leading `.` would otherwise be parsed as an operator.
+    ($out:expr => .$next:ident) => {
+        $out.$next
+    };
     // Comparison with literals
     ($out:expr => $op:tt $value:literal) => {
         ::rejis::Q!(
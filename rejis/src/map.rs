@@ -1,4 +1,6 @@
 use crate::{
+    limit::Limits,
+    order::SortKey,
     transform::{FromRow, Transform},
     Query, Queryable, Table,
 };
@@ -12,6 +14,25 @@ where
 {
     pub(crate) inner: Inner,
     pub(crate) selector: Query<Field, Root>,
+    pub(crate) distinct: bool,
+}
+
+impl<Field, Root, Inner> Select<Field, Root, Inner>
+where
+    Root: Table,
+    Field: Queryable<Root>,
+    Inner: Transform,
+{
+    /// Produce each distinct value only once, instead of once per matching object.
+    ///
+    /// Since rows are then no longer tied to single objects, operations which
+    /// identify rows by their object, such as [`Database::get_page`](crate::Database::get_page)
+    /// and [`Database::get_with_ids`](crate::Database::get_with_ids), fail with
+    /// [`TransformError::UnsupportedDistinct`](crate::transform::TransformError::UnsupportedDistinct).
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
 }
 
 impl<Field, Root, Filter> Transform for Select<Field, Root, Filter>
@@ -37,8 +58,27 @@ where
         self.inner.cte(name, f)
     }
 
+    fn columns(&self) -> String {
+        self.selector.path().json("result.value")
+    }
+
+    fn is_distinct(&self) -> bool {
+        self.distinct
+    }
+
     fn statement(&self) -> String {
-        let query = self.selector.path();
-        format!("select cast(json_extract(result.value, '{query}') as text) from result")
+        if self.distinct {
+            format!("select distinct {} from result", self.columns())
+        } else {
+            format!("select {} from result", self.columns())
+        }
+    }
+
+    fn limits(&self) -> Limits {
+        self.inner.limits()
+    }
+
+    fn ordering(&self) -> Vec<SortKey> {
+        self.inner.ordering()
     }
 }
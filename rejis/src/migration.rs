@@ -0,0 +1,146 @@
+//! Structures used for migrating stored objects between versions of their structure.
+use crate::{transform::TransformError, Table};
+use rusqlite::{types::Type, Connection};
+use serde_json::Value;
+
+/// Name of the table recording which migrations have been applied to which tables.
+const MIGRATIONS_TABLE: &str = "rejis_migrations";
+
+/// Single step in migrating all stored objects of a [`Table`] from one
+/// version of its structure to the next, see [`Table::migrations`].
+pub struct Migration(Step);
+
+enum Step {
+    Sql(String),
+    Function(Box<dyn Fn(Value) -> Value>),
+}
+
+impl Migration {
+    /// Replace each stored object with the result of the sql `expression`, in which
+    /// the current object is available as `value`, such as `json_remove(value, '$.nickname')`.
+    pub fn sql(expression: impl Into<String>) -> Self {
+        Migration(Step::Sql(expression.into()))
+    }
+
+    /// Replace each stored object with the result of `f`.
+    ///
+    /// Fails with [`rusqlite::Error::FromSqlConversionFailure`] if a stored
+    /// object is not valid json.
+    pub fn function(f: impl Fn(Value) -> Value + 'static) -> Self {
+        Migration(Step::Function(Box::new(f)))
+    }
+
+    /// Apply the step to all objects in `table`, which are then
+    /// considered to be stored as the current `version`.
+    fn apply(
+        &self,
+        connection: &Connection,
+        table: &str,
+        version: usize,
+    ) -> Result<(), rusqlite::Error> {
+        match &self.0 {
+            Step::Sql(expression) => {
+                connection.execute(
+                    &format!("update {table} set value = {expression}, version = ?1"),
+                    (version,),
+                )?;
+            }
+            Step::Function(f) => {
+                // Collect all rows before updating any of them,
+                // rather than writing to the table while reading it.
+                let rows = connection
+                    .prepare(&format!("select rowid, value from {table}"))?
+                    .query_map((), |row| {
+                        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let mut update = connection.prepare(&format!(
+                    "update {table} set value = json(?1), version = ?2 where rowid = ?3"
+                ))?;
+
+                for (rowid, value) in rows {
+                    let value = serde_json::from_str(&value).map_err(|err| {
+                        rusqlite::Error::FromSqlConversionFailure(1, Type::Text, Box::new(err))
+                    })?;
+                    let migrated = serde_json::to_string(&f(value))
+                        .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+                    update.execute((migrated, version, rowid))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Step upgrading a single stored object from one version of its structure
+/// to the next when it is read, see [`Table::upgrades`].
+pub type Upgrade = fn(Value) -> Value;
+
+/// Current version of the structure of `Root`, which is stored alongside new objects.
+pub(crate) fn version<Root: Table>() -> usize {
+    Root::upgrades().len()
+}
+
+/// Deserialize `value` stored as the given `version` of `Root`,
+/// applying any upgrades necessary to bring it up to date.
+pub(crate) fn upgrade<Root: Table>(version: usize, value: &str) -> Result<Root, TransformError> {
+    let upgrades = Root::upgrades();
+    if version >= upgrades.len() {
+        return Ok(serde_json::from_str(value)?);
+    }
+
+    let value = upgrades[version..]
+        .iter()
+        .fold(serde_json::from_str(value)?, |value, upgrade| {
+            upgrade(value)
+        });
+
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Apply the migrations of `Root` which have not yet been applied to
+/// its table, returning the number of newly applied migrations.
+///
+/// The table tracking applied migrations is only created once `Root`
+/// declares any migrations.
+///
+/// Should be run within a transaction, so either all or none of the
+/// pending migrations are applied.
+pub(crate) fn migrate<Root: Table>(connection: &Connection) -> Result<usize, rusqlite::Error> {
+    let table = Root::TABLE_NAME;
+
+    let migrations = Root::migrations();
+    if migrations.is_empty() {
+        return Ok(0);
+    }
+
+    connection.execute(
+        &format!(
+            "create table if not exists {MIGRATIONS_TABLE} (
+    table_name text not null,
+    step integer not null,
+    primary key (table_name, step)
+) strict"
+        ),
+        (),
+    )?;
+
+    let applied: usize = connection.query_row(
+        &format!("select count(*) from {MIGRATIONS_TABLE} where table_name = ?1"),
+        (table,),
+        |row| row.get(0),
+    )?;
+
+    for (step, migration) in migrations.iter().enumerate().skip(applied) {
+        migration.apply(connection, table, version::<Root>())?;
+
+        connection.execute(
+            &format!("insert into {MIGRATIONS_TABLE}(table_name, step) values (?1, ?2)"),
+            (table, step),
+        )?;
+    }
+
+    Ok(migrations.len().saturating_sub(applied))
+}
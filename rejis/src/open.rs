@@ -0,0 +1,168 @@
+//! Builder for opening connections configured for use with rejis.
+use crate::{transform::TransformError, Database, Table};
+use rusqlite::Connection;
+use std::{
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Start configuring a [`Connection`] to the database at `path`.
+///
+/// ```rust
+/// # use rejis::{open::Synchronous, Database, Table, Q};
+/// # use rejis_test_data::User;
+/// let db = rejis::open(":memory:")
+///     .synchronous(Synchronous::Full)
+///     .init::<User>()
+///     .connect()
+///     .unwrap();
+///
+/// assert_eq!(db.count(&Q! { User.age > 0 }).unwrap(), 0);
+/// ```
+pub fn open(path: impl AsRef<Path>) -> Open {
+    Open {
+        path: path.as_ref().to_path_buf(),
+        journal_mode: JournalMode::Wal,
+        synchronous: Synchronous::Normal,
+        busy_timeout: Duration::from_secs(5),
+        mmap_size: None,
+        foreign_keys: true,
+        tables: Vec::new(),
+    }
+}
+
+/// Journaling mode of the database, see [`Open::journal_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalMode {
+    Delete,
+    Truncate,
+    Persist,
+    Memory,
+    Wal,
+    Off,
+}
+
+impl Display for JournalMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            JournalMode::Delete => "delete",
+            JournalMode::Truncate => "truncate",
+            JournalMode::Persist => "persist",
+            JournalMode::Memory => "memory",
+            JournalMode::Wal => "wal",
+            JournalMode::Off => "off",
+        })
+    }
+}
+
+/// How often sqlite waits for writes to reach the disk, see [`Open::synchronous`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Synchronous {
+    Off,
+    Normal,
+    Full,
+    Extra,
+}
+
+impl Display for Synchronous {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Synchronous::Off => "off",
+            Synchronous::Normal => "normal",
+            Synchronous::Full => "full",
+            Synchronous::Extra => "extra",
+        })
+    }
+}
+
+/// Initialization of a single table, see [`Open::init`].
+type Init = fn(&Connection) -> Result<usize, TransformError>;
+
+/// Configuration of a [`Connection`] to be opened, constructed using [`open`].
+///
+/// Defaults to WAL journaling with `synchronous = normal`, a busy timeout of
+/// five seconds, and foreign keys enabled.
+pub struct Open {
+    path: PathBuf,
+    journal_mode: JournalMode,
+    synchronous: Synchronous,
+    busy_timeout: Duration,
+    mmap_size: Option<u64>,
+    foreign_keys: bool,
+    tables: Vec<Init>,
+}
+
+impl Open {
+    /// Set the `journal_mode` pragma.
+    pub fn journal_mode(mut self, journal_mode: JournalMode) -> Self {
+        self.journal_mode = journal_mode;
+        self
+    }
+
+    /// Set the `synchronous` pragma.
+    pub fn synchronous(mut self, synchronous: Synchronous) -> Self {
+        self.synchronous = synchronous;
+        self
+    }
+
+    /// Set how long to wait for locks held by other connections to be released.
+    pub fn busy_timeout(mut self, busy_timeout: Duration) -> Self {
+        self.busy_timeout = busy_timeout;
+        self
+    }
+
+    /// Set the `mmap_size` pragma, the maximum number of bytes of the database
+    /// file to access using memory-mapped I/O.
+    pub fn mmap_size(mut self, mmap_size: u64) -> Self {
+        self.mmap_size = Some(mmap_size);
+        self
+    }
+
+    /// Set the `foreign_keys` pragma.
+    pub fn foreign_keys(mut self, foreign_keys: bool) -> Self {
+        self.foreign_keys = foreign_keys;
+        self
+    }
+
+    /// Initialize the table of `Root` using [`Database::init`] once connected.
+    pub fn init<Root: Table>(mut self) -> Self {
+        self.tables
+            .push(|connection| Ok(connection.init::<Root>()?));
+        self
+    }
+
+    /// Open the connection, apply the configured pragmas and initialize tables.
+    ///
+    /// Fails with [`TransformError::MissingJson`] if sqlite was built without
+    /// the json functions all queries depend upon.
+    pub fn connect(self) -> Result<Connection, TransformError> {
+        let connection = Connection::open(&self.path)?;
+
+        connection
+            .query_row("select json_valid('{}')", (), |_| Ok(()))
+            .map_err(|_| TransformError::MissingJson)?;
+
+        let mut pragmas = format!(
+            "pragma journal_mode = {journal_mode};
+pragma synchronous = {synchronous};
+pragma foreign_keys = {foreign_keys};",
+            journal_mode = self.journal_mode,
+            synchronous = self.synchronous,
+            foreign_keys = self.foreign_keys
+        );
+
+        if let Some(mmap_size) = self.mmap_size {
+            pragmas.push_str(&format!("\npragma mmap_size = {mmap_size};"));
+        }
+
+        connection.execute_batch(&pragmas)?;
+        connection.busy_timeout(self.busy_timeout)?;
+
+        for init in self.tables {
+            init(&connection)?;
+        }
+
+        Ok(connection)
+    }
+}
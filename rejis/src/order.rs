@@ -0,0 +1,147 @@
+//! Structures used for ordering the results of queries.
+use crate::{
+    limit::Limits,
+    transform::{FromRow, Transform, TransformError},
+    Path,
+};
+use rusqlite::{Row, Statement};
+use std::fmt::{Display, Formatter};
+
+/// Direction in which a [`SortKey`] is ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Direction::Asc => "asc",
+            Direction::Desc => "desc",
+        })
+    }
+}
+
+/// Placement of `null` values when ordering by a [`SortKey`].
+///
+/// If not specified, sqlite considers `null` smaller than any other value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nulls {
+    First,
+    Last,
+}
+
+impl Display for Nulls {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Nulls::First => "nulls first",
+            Nulls::Last => "nulls last",
+        })
+    }
+}
+
+/// Describes ordering of results by the value at a json path.
+#[derive(Debug, Clone)]
+pub struct SortKey {
+    pub path: Path,
+    pub direction: Direction,
+    pub nulls: Option<Nulls>,
+}
+
+impl Display for SortKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "json_extract(result.value, '{path}') {direction}",
+            path = self.path,
+            direction = self.direction
+        )?;
+
+        if let Some(nulls) = self.nulls {
+            write!(f, " {nulls}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Clause appended to the final statement for ordering by `keys`.
+pub(crate) fn clause(keys: &[SortKey]) -> String {
+    if keys.is_empty() {
+        return String::new();
+    }
+
+    let keys: Vec<_> = keys.iter().map(ToString::to_string).collect();
+    format!("\norder by {}", keys.join(", "))
+}
+
+/// Orders the results of the `Inner` transform by an additional [`SortKey`].
+///
+/// Keys are applied in the order they were added, so the
+/// first call to [`Transform::order_by`] determines the primary key.
+pub struct OrderBy<Inner>
+where
+    Inner: Transform,
+{
+    pub(crate) inner: Inner,
+    pub(crate) key: SortKey,
+}
+
+impl<Inner> OrderBy<Inner>
+where
+    Inner: Transform,
+{
+    /// Place `null` values first or last when ordering by this key.
+    pub fn nulls(mut self, nulls: Nulls) -> Self {
+        self.key.nulls = Some(nulls);
+        self
+    }
+}
+
+impl<Inner> Transform for OrderBy<Inner>
+where
+    Inner: Transform,
+{
+    type Root = Inner::Root;
+    type Field = Inner::Field;
+    type Output = Inner::Output;
+
+    fn bind(
+        &self,
+        statement: &mut Statement<'_>,
+        index: &mut usize,
+    ) -> Result<(), rusqlite::Error> {
+        self.inner.bind(statement, index)
+    }
+
+    fn cte(&self, name: &str, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        self.inner.cte(name, f)
+    }
+
+    fn columns(&self) -> String {
+        self.inner.columns()
+    }
+
+    fn statement(&self) -> String {
+        self.inner.statement()
+    }
+
+    fn is_distinct(&self) -> bool {
+        self.inner.is_distinct()
+    }
+
+    fn extract(&self, row: &Row) -> Result<<Self::Output as FromRow>::Output, TransformError> {
+        self.inner.extract(row)
+    }
+
+    fn limits(&self) -> Limits {
+        self.inner.limits()
+    }
+
+    fn ordering(&self) -> Vec<SortKey> {
+        let mut keys = self.inner.ordering();
+        keys.push(self.key.clone());
+        keys
+    }
+}
@@ -5,7 +5,7 @@ use serde::{de::DeserializeOwned, Serialize};
 pub mod path {
     use std::fmt::Display;
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
     pub enum PathElement {
         Field(&'static str),
         Index(usize),
@@ -36,7 +36,7 @@ pub mod path {
         }
     }
 
-    #[derive(Default, Debug, Clone)]
+    #[derive(Default, Debug, Clone, PartialEq, Eq)]
     pub struct Path(Vec<PathElement>);
 
     impl Path {
@@ -45,6 +45,41 @@ pub mod path {
             path.push(element.into());
             Path(path)
         }
+
+        /// Sql expression extracting the value at this path from the json in `column`.
+        ///
+        /// Used for both filtering and indexing, since sqlite only uses an
+        /// expression index if the filter expression matches it exactly.
+        pub(crate) fn extract(&self, column: &str) -> String {
+            format!("json_extract({column}, '{self}')")
+        }
+
+        /// Sql expression producing the json text of the value at this path in
+        /// `column`, or `null` if there is none.
+        ///
+        /// Unlike [`Path::extract`], this preserves json types which sqlite has no
+        /// equivalent of, such as booleans, which `json_extract` turns into integers.
+        pub(crate) fn json(&self, column: &str) -> String {
+            format!("coalesce({column} -> '{self}', 'null')")
+        }
+
+        /// Quoted name of the generated column materializing this path, see
+        /// [`Path::identifier`], which might otherwise be a keyword of sql.
+        pub(crate) fn column(&self) -> String {
+            format!("\"{}\"", self.identifier().replace('"', "\"\""))
+        }
+
+        /// Identifier derived from this path, for use in names of indices and columns.
+        pub(crate) fn identifier(&self) -> String {
+            self.0
+                .iter()
+                .map(|element| match element {
+                    PathElement::Field(field) => field.to_string(),
+                    PathElement::Index(index) => index.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("_")
+        }
     }
 
     impl Display for Path {
@@ -72,7 +107,9 @@ pub mod path {
 }
 
 use crate::{
+    facet::Facet,
     filter::{Any, Comparison, Operator},
+    update::Set,
     Table,
 };
 use std::{fmt::Debug, marker::PhantomData, ops::Deref};
@@ -172,6 +209,17 @@ where
         }
     }
 
+    /// Set value at query's path to `value`, when applied as an [`Update`](crate::update::Update).
+    pub fn set<Value: Into<<Field::QueryType as QueryConstructor<Root>>::Inner>>(
+        &self,
+        value: Value,
+    ) -> Set<Field, Root> {
+        Set {
+            query: self.clone(),
+            value: value.into(),
+        }
+    }
+
     /// Construct a dot-separated json-path from this query.
     pub fn path(&self) -> &Path {
         &self.path
@@ -206,6 +254,24 @@ where
             value: value.into(),
         }
     }
+
+    /// Describe the values at the path selected by `f` within each element of this
+    /// array, for counting using [`Database::facets`](crate::Database::facets).
+    pub fn facet<
+        InnerField: Queryable<Root>,
+        F: FnOnce(Query<Field, Root>) -> Query<InnerField, Root>,
+    >(
+        &self,
+        f: F,
+    ) -> Facet<Root> {
+        let indexed = VecField::<Field, Root>::new::<Field>(&Path::default()).wildcard();
+
+        Facet {
+            outer_path: self.path.clone(),
+            inner_path: f(indexed).path,
+            _data: PhantomData,
+        }
+    }
 }
 
 /// This is a hack, allowing us to step right through the `Query` abstraction
@@ -286,6 +352,55 @@ unit_field_impl!(i128, I128Query);
 unit_field_impl!(isize, IsizeQuery);
 unit_field_impl!(bool, BoolQuery);
 
+/// A struct field of type [`Option`], which [`Deref`]s into the query type
+/// of the contained value.
+pub struct OptionField<Field, Root>(Field::QueryType, PhantomData<Root>)
+where
+    Field: Queryable<Root>,
+    Root: Table;
+
+impl<Field, Root> Clone for OptionField<Field, Root>
+where
+    Field: Queryable<Root>,
+    Root: Table,
+{
+    fn clone(&self) -> Self {
+        OptionField(self.0.clone(), PhantomData)
+    }
+}
+
+impl<Field, Root> Queryable<Root> for Option<Field>
+where
+    Field: Queryable<Root>,
+    Root: Table,
+{
+    type QueryType = OptionField<Field, Root>;
+}
+
+impl<T, Root> QueryConstructor<Root> for OptionField<T, Root>
+where
+    T: Queryable<Root>,
+    Root: Table,
+{
+    type Inner = Option<T>;
+
+    fn new<Field: Queryable<Root>>(path: &Path) -> Self {
+        OptionField(T::QueryType::new::<Field>(path), PhantomData)
+    }
+}
+
+impl<T, Root> Deref for OptionField<T, Root>
+where
+    T: Queryable<Root>,
+    Root: Table,
+{
+    type Target = T::QueryType;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 /// A struct field of type [`Vec`]
 #[derive(Debug, Clone)]
 pub struct VecField<Field, Root>(Query<Field, Root>)
@@ -0,0 +1,220 @@
+//! Thread-safe access to a database file shared by a writer and multiple readers.
+use crate::{
+    database::with_savepoint,
+    transform::{FromRow, Transform, TransformError},
+    update::Update,
+    Database, Id, Table,
+};
+use rusqlite::{Connection, ErrorCode, OpenFlags};
+use std::{
+    ops::Deref,
+    path::Path,
+    sync::{Condvar, Mutex, MutexGuard},
+    thread,
+    time::Duration,
+};
+
+/// Number of times an operation is retried after failing with `SQLITE_BUSY`.
+const BUSY_RETRIES: u32 = 8;
+
+/// Shareable handle to a database file, which can be used from multiple threads.
+///
+/// Opens a single write connection and a pool of read-only connections to the
+/// same file in WAL mode, so reads can proceed concurrently with each other and
+/// with the writer. Queries are routed to the readers, while mutations are
+/// serialized through the writer.
+///
+/// Operations failing because the database is busy are retried with an
+/// increasing delay, before giving up and returning the error.
+///
+/// ```rust
+/// # use rejis::{Table, Q, Store};
+/// # use rejis_test_data::User;
+/// # let path = std::env::temp_dir()
+/// #     .join(format!("rejis-store-doctest-{pid}.db", pid = std::process::id()));
+/// let store = Store::open(&path, 4).unwrap();
+/// store.init::<User>().unwrap();
+///
+/// let users = store.get(&Q! { User.last_name == "Smith" }).unwrap();
+/// assert!(users.is_empty());
+/// # drop(store);
+/// # for suffix in ["", "-wal", "-shm"] {
+/// #     let mut file = path.clone().into_os_string();
+/// #     file.push(suffix);
+/// #     let _ = std::fs::remove_file(file);
+/// # }
+/// ```
+pub struct Store {
+    writer: Mutex<Connection>,
+    readers: Mutex<Vec<Connection>>,
+    available: Condvar,
+}
+
+impl Store {
+    /// Open the database at `path` with one writer and `readers` read-only
+    /// connections, creating the file if it does not exist.
+    pub fn open(path: impl AsRef<Path>, readers: usize) -> Result<Self, rusqlite::Error> {
+        let path = path.as_ref();
+
+        // The writer must switch the database to WAL mode before any
+        // readers are opened, since read-only connections cannot.
+        let writer = Connection::open(path)?;
+        writer.query_row("pragma journal_mode = wal", (), |_| Ok(()))?;
+
+        let readers = (0..readers.max(1))
+            .map(|_| {
+                Connection::open_with_flags(
+                    path,
+                    OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+                )
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Store {
+            writer: Mutex::new(writer),
+            readers: Mutex::new(readers),
+            available: Condvar::new(),
+        })
+    }
+
+    /// Run `f` using one of the read-only connections, waiting for
+    /// one to become available if all are in use.
+    pub fn read<R>(
+        &self,
+        mut f: impl FnMut(&Connection) -> Result<R, TransformError>,
+    ) -> Result<R, TransformError> {
+        let reader = Reader::checkout(self);
+        retry_busy(|| f(&reader))
+    }
+
+    /// Run `f` using the write connection, waiting for other writes to finish.
+    ///
+    /// Each attempt runs within a savepoint, so changes made by `f` are rolled
+    /// back if it fails, and are not applied twice if it is retried.
+    pub fn write<R>(
+        &self,
+        mut f: impl FnMut(&Connection) -> Result<R, TransformError>,
+    ) -> Result<R, TransformError> {
+        let writer = lock(&self.writer);
+        retry_busy(|| with_savepoint(&writer, || f(&writer)))
+    }
+
+    /// See [`Database::init`].
+    pub fn init<Root: Table>(&self) -> Result<usize, TransformError> {
+        self.write(|connection| Ok(connection.init::<Root>()?))
+    }
+
+    /// See [`Database::insert`].
+    pub fn insert<Root: Table>(&self, value: &Root) -> Result<Id<Root>, TransformError> {
+        self.write(|connection| connection.insert(value))
+    }
+
+    /// See [`Database::upsert`].
+    pub fn upsert<Root: Table>(&self, value: &Root) -> Result<Id<Root>, TransformError> {
+        self.write(|connection| connection.upsert(value))
+    }
+
+    /// See [`Database::get`].
+    pub fn get<T: Transform>(
+        &self,
+        transform: &T,
+    ) -> Result<Vec<<<T as Transform>::Output as FromRow>::Output>, TransformError> {
+        self.read(|connection| connection.get(transform))
+    }
+
+    /// See [`Database::count`].
+    pub fn count<T: Transform>(&self, transform: &T) -> Result<usize, TransformError> {
+        self.read(|connection| connection.count(transform))
+    }
+
+    /// See [`Database::delete`].
+    pub fn delete<T: Transform>(&self, filter: &T) -> Result<usize, TransformError> {
+        self.write(|connection| connection.delete(filter))
+    }
+
+    /// See [`Database::replace`].
+    pub fn replace<T: Transform>(
+        &self,
+        filter: &T,
+        value: &<T as Transform>::Root,
+    ) -> Result<usize, TransformError> {
+        self.write(|connection| connection.replace(filter, value))
+    }
+
+    /// See [`Database::update`].
+    pub fn update<T, U>(&self, filter: &T, update: &U) -> Result<usize, TransformError>
+    where
+        T: Transform,
+        U: Update<Root = <T as Transform>::Root>,
+    {
+        self.write(|connection| connection.update(filter, update))
+    }
+}
+
+/// Read-only connection checked out of the pool of a [`Store`],
+/// which is returned to the pool when dropped.
+struct Reader<'a> {
+    store: &'a Store,
+    connection: Option<Connection>,
+}
+
+impl<'a> Reader<'a> {
+    fn checkout(store: &'a Store) -> Self {
+        let mut readers = lock(&store.readers);
+        loop {
+            if let Some(connection) = readers.pop() {
+                return Reader {
+                    store,
+                    connection: Some(connection),
+                };
+            }
+
+            readers = store
+                .available
+                .wait(readers)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+}
+
+impl Deref for Reader<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        // Only taken when the reader is dropped.
+        self.connection.as_ref().unwrap()
+    }
+}
+
+impl Drop for Reader<'_> {
+    fn drop(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            lock(&self.store.readers).push(connection);
+            self.store.available.notify_one();
+        }
+    }
+}
+
+/// Lock `mutex`, ignoring poisoning, since a panic while using a
+/// connection does not leave the [`Store`] itself in an inconsistent state.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Run `f`, retrying with an increasing delay while it fails with `SQLITE_BUSY`.
+fn retry_busy<R>(mut f: impl FnMut() -> Result<R, TransformError>) -> Result<R, TransformError> {
+    let mut attempt = 0;
+    loop {
+        match f() {
+            Err(TransformError::Sql(rusqlite::Error::SqliteFailure(error, _)))
+                if error.code == ErrorCode::DatabaseBusy && attempt < BUSY_RETRIES =>
+            {
+                thread::sleep(Duration::from_millis(1 << attempt));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
@@ -1,15 +1,122 @@
 use serde::Serialize;
 
-use crate::{Query, Queryable};
+use crate::{
+    migration::{Migration, Upgrade},
+    Path, Query, Queryable,
+};
 
 /// Describes how to store the type for which it is implemented
 /// in an sqlite table.
+///
+/// When derived, fields can be marked using `#[rejis(key)]`, `#[rejis(index)]` and
+/// `#[rejis(generated)]`. Unsupported or conflicting attributes are reported as errors:
+/// ```compile_fail
+/// # use rejis::{Queryable, Table};
+/// # use serde::{Serialize, Deserialize};
+/// #[derive(Queryable, Table, Serialize, Deserialize, Clone)]
+/// struct Account {
+///     #[rejis(primary)]
+///     email: String,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use rejis::{Queryable, Table};
+/// # use serde::{Serialize, Deserialize};
+/// #[derive(Queryable, Table, Serialize, Deserialize, Clone)]
+/// struct Account {
+///     #[rejis(key)]
+///     email: String,
+///     #[rejis(key)]
+///     username: String,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use rejis::{Queryable, Table};
+/// # use serde::{Serialize, Deserialize};
+/// #[derive(Queryable, Table, Serialize, Deserialize, Clone)]
+/// struct Setting {
+///     #[rejis(generated)]
+///     value: String,
+/// }
+/// ```
 pub trait Table: Queryable<Self> + Serialize + Sized + 'static {
     /// Name used for the table in the database when reading or writing
     /// this object to it.
     const TABLE_NAME: &'static str;
 
+    /// Json path of the field which uniquely identifies an object within
+    /// the table, if any.
+    ///
+    /// A unique index is created over this path by [`Database::init`](crate::Database::init),
+    /// and it is used for resolving conflicts in [`Database::upsert`](crate::Database::upsert).
+    fn key() -> Option<Path> {
+        None
+    }
+
+    /// Json paths of fields to create indices over using [`Database::init`](crate::Database::init),
+    /// see [`Database::create_index`](crate::Database::create_index).
+    fn indices() -> Vec<Path> {
+        Vec::new()
+    }
+
+    /// Json paths of fields to materialize as stored generated columns, which are
+    /// created by [`Database::init`](crate::Database::init).
+    ///
+    /// Filters over these fields compare against the column directly, instead of
+    /// extracting the field from each object, while the object remains the source
+    /// of truth. Columns are named after the path, as in `pets_0_name` for
+    /// `$.pets[0].name`, so the names of paths must be distinct from each other,
+    /// as well as from `value`, `version` and the aliases of the rowid, ignoring
+    /// case. [`Database::init`](crate::Database::init) fails otherwise, while
+    /// deriving reports such fields as errors.
+    fn generated() -> Vec<Path> {
+        Vec::new()
+    }
+
+    /// Ordered steps migrating stored objects from previous versions of their
+    /// structure to the current one.
+    ///
+    /// Steps which have not yet been applied to the table are run by
+    /// [`Database::init`](crate::Database::init). Steps must only ever be
+    /// appended, since applied steps are tracked by their position.
+    fn migrations() -> Vec<Migration> {
+        Vec::new()
+    }
+
+    /// Ordered steps upgrading a single stored object from previous versions
+    /// of its structure to the current one, as it is read.
+    ///
+    /// The current version is the number of upgrades, and is stored alongside
+    /// each object when it is written, so only the steps following the stored
+    /// version are applied. Unlike [`Table::migrations`], this does not require
+    /// rewriting the whole table at once.
+    ///
+    /// Filters, orderings and other queries over fields are evaluated against
+    /// objects as they are stored, not in their upgraded form, until they are
+    /// written back using [`Database::upgrade_all`](crate::Database::upgrade_all).
+    fn upgrades() -> Vec<Upgrade> {
+        Vec::new()
+    }
+
     fn query() -> Query<Self, Self> {
         Query::<Self, Self>::default()
     }
 }
+
+/// Sql expression for the value at `path` within objects of `Root`, using the
+/// generated column materializing it if declared by [`Table::generated`], or
+/// extracting it from the json otherwise. Columns are qualified by `source`, if any.
+pub(crate) fn expression<Root: Table>(path: &Path, source: Option<&str>) -> String {
+    let column = |name: &str| match source {
+        Some(source) => format!("{source}.{name}"),
+        None => name.to_string(),
+    };
+
+    if Root::generated().contains(path) {
+        column(&path.column())
+    } else {
+        path.extract(&column("value"))
+    }
+}
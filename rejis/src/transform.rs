@@ -1,12 +1,38 @@
 use rusqlite::{Row, Statement};
 use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
 
-use crate::{map::Select, Query, Queryable, Table};
+use crate::{
+    limit::{Limit, Limits},
+    map::Select,
+    migration,
+    order::{Direction, OrderBy, SortKey},
+    Query, Queryable, Table,
+};
 
 #[derive(Debug)]
 pub enum TransformError {
     Sql(rusqlite::Error),
     Serde(serde_json::Error),
+    /// The [`Table`] with the given name does not declare a [`Table::key`].
+    MissingKey(&'static str),
+    /// No items matched a query expecting exactly one result.
+    NotFound,
+    /// More than one item matched a query expecting at most one result.
+    MultipleResults,
+    /// A distinct selection was used where each row must identify its object,
+    /// as in [`Database::get_page`](crate::Database::get_page) and
+    /// [`Database::get_with_ids`](crate::Database::get_with_ids).
+    UnsupportedDistinct,
+    /// Keyset pagination was attempted over sort keys with differing directions.
+    UnsupportedOrdering,
+    /// The cursor used for keyset pagination does not match the sort keys of the query.
+    InvalidCursor,
+    /// The sqlite library was built without support for json functions.
+    MissingJson,
+    /// The worker thread of an [`AsyncDatabase`](crate::AsyncDatabase) stopped,
+    /// or the operation panicked, before the operation completed.
+    WorkerStopped,
 }
 
 impl From<rusqlite::Error> for TransformError {
@@ -30,14 +56,91 @@ pub trait Transform {
         -> Result<(), rusqlite::Error>;
     fn cte(&self, name: &str, f: &mut impl std::fmt::Write) -> std::fmt::Result;
 
+    /// Columns selected from the `result` CTE by the final statement.
+    ///
+    /// Includes the version each object was stored as if `Root` declares any
+    /// [upgrades](Table::upgrades), so [`Document`] can upgrade objects
+    /// stored as previous versions.
+    fn columns(&self) -> String {
+        if migration::version::<Self::Root>() == 0 {
+            String::from("result.value")
+        } else {
+            String::from("result.value, result.version")
+        }
+    }
+
     fn statement(&self) -> String {
-        String::from("select result.value from result")
+        format!("select {} from result", self.columns())
+    }
+
+    /// Whether the final statement produces each distinct row only once,
+    /// in which case its rows cannot be identified by their objects.
+    fn is_distinct(&self) -> bool {
+        false
     }
 
     fn extract(&self, row: &Row) -> Result<<Self::Output as FromRow>::Output, TransformError> {
         Self::Output::from_row(row)
     }
 
+    /// Limits applied to the final statement, see [`Transform::limit`].
+    fn limits(&self) -> Limits {
+        Limits::default()
+    }
+
+    /// Produce at most `limit` results.
+    fn limit(self, limit: usize) -> Limit<Self>
+    where
+        Self: Sized,
+    {
+        Limit {
+            inner: self,
+            limits: Limits {
+                limit: Some(limit),
+                offset: None,
+            },
+        }
+    }
+
+    /// Skip the first `offset` results.
+    fn offset(self, offset: usize) -> Limit<Self>
+    where
+        Self: Sized,
+    {
+        Limit {
+            inner: self,
+            limits: Limits {
+                limit: None,
+                offset: Some(offset),
+            },
+        }
+    }
+
+    /// Sort keys applied to the final statement, see [`Transform::order_by`].
+    fn ordering(&self) -> Vec<SortKey> {
+        Vec::new()
+    }
+
+    /// Order results by the value at `query`'s path. Can be called
+    /// repeatedly to add further keys for ordering otherwise equal results.
+    fn order_by<Subfield: Queryable<Self::Root>>(
+        self,
+        query: &Query<Subfield, Self::Root>,
+        direction: Direction,
+    ) -> OrderBy<Self>
+    where
+        Self: Sized,
+    {
+        OrderBy {
+            inner: self,
+            key: SortKey {
+                path: query.path().clone(),
+                direction,
+                nulls: None,
+            },
+        }
+    }
+
     fn map<Subfield: Queryable<Self::Root>>(
         self,
         query: &Query<Subfield, Self::Root>,
@@ -48,6 +151,7 @@ pub trait Transform {
         Select {
             selector: query.clone(),
             inner: self,
+            distinct: false,
         }
     }
 }
@@ -67,3 +171,23 @@ where
         Ok(serde_json::from_str(&value)?)
     }
 }
+
+/// Output of transforms producing whole `Root` objects, which are upgraded
+/// from the version they were stored as using [`Table::upgrades`].
+pub struct Document<Root>(PhantomData<fn() -> Root>);
+
+impl<Root> FromRow for Document<Root>
+where
+    Root: Table,
+{
+    type Output = Root;
+    fn from_row(row: &Row) -> Result<Self::Output, TransformError> {
+        let value: String = row.get(0)?;
+        if migration::version::<Root>() == 0 {
+            return Ok(serde_json::from_str(&value)?);
+        }
+
+        let version: usize = row.get(1)?;
+        migration::upgrade(version, &value)
+    }
+}
@@ -0,0 +1,71 @@
+//! Structures used for updating stored objects in-place.
+use crate::{Query, QueryConstructor, Queryable, Table};
+use rusqlite::Statement;
+use std::fmt::{Debug, Formatter};
+
+/// Describes an in-place modification of the json value of a `Root` object,
+/// which can be applied to all objects matched by a filter using
+/// [`Database::update`](crate::Database::update).
+pub trait Update {
+    type Root: Table;
+
+    fn bind(&self, statement: &mut Statement<'_>, index: &mut usize)
+        -> Result<(), rusqlite::Error>;
+
+    /// Write the sql expression computing the updated version of `column`.
+    fn expression(&self, column: &str, f: &mut impl std::fmt::Write) -> std::fmt::Result;
+}
+
+/// Sets the value at the `Query` path of a `Root` object to the given value.
+///
+/// The value is stored as json, so it deserializes back into the same type.
+pub struct Set<Field, Root>
+where
+    Field: Queryable<Root>,
+    Root: Table,
+{
+    pub(crate) query: Query<Field, Root>,
+    pub(crate) value: <Field::QueryType as QueryConstructor<Root>>::Inner,
+}
+
+impl<Field: Debug, Root: Debug> Debug for Set<Field, Root>
+where
+    Field: Queryable<Root>,
+    Root: Table,
+    <Field::QueryType as QueryConstructor<Root>>::Inner: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Set")
+            .field("query", &self.query)
+            .field("value", &self.value as &dyn Debug)
+            .finish()
+    }
+}
+
+impl<Field, Root> Update for Set<Field, Root>
+where
+    Field: Queryable<Root>,
+    Root: Table,
+{
+    type Root = Root;
+
+    fn bind(
+        &self,
+        statement: &mut Statement<'_>,
+        index: &mut usize,
+    ) -> Result<(), rusqlite::Error> {
+        let value = serde_json::to_string(&self.value)
+            .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        statement.raw_bind_parameter(*index, value)?;
+        *index += 1;
+        Ok(())
+    }
+
+    fn expression(&self, column: &str, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        write!(
+            f,
+            "json_set({column}, '{path}', json(?))",
+            path = self.query.path()
+        )
+    }
+}
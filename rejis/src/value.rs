@@ -0,0 +1,29 @@
+//! Conversion between sqlite values and their json equivalents.
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use serde_json::{Number, Value};
+
+/// Convert a value produced by sqlite, such as the result of `json_extract`, into json.
+pub(crate) fn to_json(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(integer) => Value::from(integer),
+        ValueRef::Real(real) => Number::from_f64(real).map_or(Value::Null, Value::Number),
+        ValueRef::Text(text) => Value::String(String::from_utf8_lossy(text).into_owned()),
+        ValueRef::Blob(blob) => Value::from(blob),
+    }
+}
+
+/// Convert json into the value sqlite would produce for it using `json_extract`.
+pub(crate) fn to_sql(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(boolean) => SqlValue::Integer(*boolean as i64),
+        Value::Number(number) => number
+            .as_i64()
+            .map(SqlValue::Integer)
+            .or_else(|| number.as_f64().map(SqlValue::Real))
+            .unwrap_or(SqlValue::Null),
+        Value::String(string) => SqlValue::Text(string.clone()),
+        Value::Array(_) | Value::Object(_) => SqlValue::Text(value.to_string()),
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod aggregates {
+    use rejis::{
+        aggregate::{Avg, CountDistinct, Max, Min, Sum},
+        transform::Transform,
+        Database, Queryable, Table, Q,
+    };
+    use rejis_test_data::{user_database, User};
+    use rusqlite::Connection;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+    struct Feature {
+        name: String,
+        release: u32,
+        enabled: bool,
+    }
+
+    fn old_user(first_name: &str) -> User {
+        User {
+            first_name: first_name.to_string(),
+            last_name: String::from("Methuselah"),
+            age: 200,
+            pets: vec![],
+        }
+    }
+
+    #[test]
+    fn numeric_aggregates() {
+        let db = user_database();
+        let smiths = Q! { User.last_name == "Smith" };
+
+        assert_eq!(db.aggregate(&smiths, Sum(&User::query().age)).unwrap(), 67);
+        assert_eq!(
+            db.aggregate(&smiths, Avg(&User::query().age)).unwrap(),
+            Some(33.5)
+        );
+        assert_eq!(
+            db.aggregate(&smiths, Min(&User::query().age)).unwrap(),
+            Some(32)
+        );
+        assert_eq!(
+            db.aggregate(&smiths, Max(&User::query().age)).unwrap(),
+            Some(35)
+        );
+    }
+
+    #[test]
+    fn string_aggregates() {
+        let db = user_database();
+        let everyone = Q! { User.age > 0 };
+
+        assert_eq!(
+            db.aggregate(&everyone, Min(&User::query().last_name))
+                .unwrap(),
+            Some(String::from("Anderson"))
+        );
+        assert_eq!(
+            db.aggregate(&everyone, CountDistinct(&User::query().last_name))
+                .unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn aggregate_nothing() {
+        let db = user_database();
+        let nobody = Q! { User.age > 100 };
+
+        assert_eq!(db.aggregate(&nobody, Sum(&User::query().age)).unwrap(), 0);
+        assert_eq!(
+            db.aggregate(&nobody, Avg(&User::query().age)).unwrap(),
+            None
+        );
+        assert_eq!(
+            db.aggregate(&nobody, Max(&User::query().age)).unwrap(),
+            None
+        );
+        assert_eq!(
+            db.aggregate(&nobody, CountDistinct(&User::query().age))
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn aggregate_limited() {
+        let db = user_database();
+        let youngest = Q! { User.age > 0 }
+            .order_by(&User::query().age, rejis::order::Direction::Asc)
+            .limit(2);
+
+        assert_eq!(
+            db.aggregate(&youngest, Sum(&User::query().age)).unwrap(),
+            56
+        );
+    }
+
+    #[test]
+    fn sum_exceeding_field_type() {
+        let db = user_database();
+        db.insert_many(&[old_user("Abe"), old_user("Bea")]).unwrap();
+
+        let methuselahs = Q! { User.last_name == "Methuselah" };
+        assert_eq!(
+            db.aggregate(&methuselahs, Sum(&User::query().age)).unwrap(),
+            400i64
+        );
+    }
+
+    #[test]
+    fn bool_aggregates() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Feature>().unwrap();
+
+        db.insert_many(&[
+            Feature {
+                name: String::from("search"),
+                release: 1,
+                enabled: true,
+            },
+            Feature {
+                name: String::from("export"),
+                release: 2,
+                enabled: false,
+            },
+        ])
+        .unwrap();
+
+        let everything = Q! { Feature.release > 0u32 };
+        assert_eq!(
+            db.aggregate(&everything, Max(&Feature::query().enabled))
+                .unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            db.aggregate(&everything, Min(&Feature::query().enabled))
+                .unwrap(),
+            Some(false)
+        );
+
+        let released = Q! { Feature.release > 1u32 };
+        assert_eq!(
+            db.aggregate(&released, Max(&Feature::query().enabled))
+                .unwrap(),
+            Some(false)
+        );
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(all(feature = "async", feature = "macros", feature = "derive"))]
+mod asynchronous {
+    use futures_executor::block_on;
+    use rejis::{
+        transform::{Transform, TransformError},
+        AsyncDatabase, Database, Table, Q,
+    };
+    use rejis_test_data::{user_database, Pet, User};
+    use rusqlite::Connection;
+
+    #[test]
+    fn init_insert_and_get() {
+        let db = AsyncDatabase::new(Connection::open_in_memory().unwrap());
+
+        block_on(db.init::<User>()).unwrap();
+        block_on(db.insert(User {
+            first_name: String::from("Bobby"),
+            last_name: String::from("Tables"),
+            age: 10,
+            pets: vec![Pet {
+                name: String::from("Robert"),
+            }],
+        }))
+        .unwrap();
+
+        let ages =
+            block_on(db.get(Q! { User.first_name == "Bobby" }.map(&User::query().age))).unwrap();
+        assert_eq!(ages, vec![10]);
+    }
+
+    #[test]
+    fn operations_run_in_order() {
+        let db = AsyncDatabase::new(user_database());
+
+        // Issue both operations before awaiting either of them.
+        let deleted = db.delete(Q! { User.last_name == "Smith" });
+        let remaining = db.get(Q! { User.age > 0 });
+
+        assert_eq!(block_on(deleted).unwrap(), 2);
+        assert_eq!(block_on(remaining).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        let db = AsyncDatabase::new(user_database());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let db = db.clone();
+                std::thread::spawn(move || {
+                    block_on(db.call(|connection| connection.count(&Q! { User.age > 30 })))
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap().unwrap().unwrap(), 4);
+        }
+    }
+
+    #[test]
+    fn panicking_operation() {
+        let db = AsyncDatabase::new(user_database());
+
+        let result = block_on(db.call(|_| -> usize { panic!("operation failed") }));
+        assert!(matches!(result, Err(TransformError::WorkerStopped)));
+
+        // The worker carries on with later operations.
+        assert_eq!(block_on(db.get(Q! { User.age > 0 })).unwrap().len(), 5);
+    }
+}
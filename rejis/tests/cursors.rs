@@ -0,0 +1,160 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod cursors {
+    use rejis::{
+        order::{
+            Direction::{Asc, Desc},
+            Nulls,
+        },
+        transform::{Transform, TransformError},
+        Database, Table, Q,
+    };
+    use rejis_test_data::{user_database, User};
+    use rusqlite::Connection;
+
+    /// First names of all users matched by `query`, retrieved one page at a time.
+    fn page_all<T: Transform<Root = User, Output = rejis::transform::Document<User>>>(
+        db: &Connection,
+        query: &T,
+    ) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (page, next) = db.get_page(query, cursor, 1).unwrap();
+            names.extend(page.into_iter().map(|user| user.first_name));
+
+            cursor = next;
+            if cursor.is_none() {
+                return names;
+            }
+        }
+    }
+
+    #[test]
+    fn page_through_ascending() {
+        let db = user_database();
+        let query = Q! { User.age > 0 }
+            .order_by(&User::query().age, Asc)
+            .map(&User::query().age);
+
+        let (first, cursor) = db.get_page(&query, None, 2).unwrap();
+        assert_eq!(first, vec![24, 32]);
+
+        let (second, cursor) = db.get_page(&query, cursor, 2).unwrap();
+        assert_eq!(second, vec![35, 36]);
+
+        let (last, cursor) = db.get_page(&query, cursor, 2).unwrap();
+        assert_eq!(last, vec![48]);
+        assert!(cursor.is_none());
+    }
+
+    #[test]
+    fn page_through_descending_with_ties() {
+        let db = user_database();
+        let query = Q! { User.age > 0 }.order_by(&User::query().last_name, Desc);
+
+        let mut names = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (page, next) = db.get_page(&query, cursor, 1).unwrap();
+            names.extend(page.into_iter().map(|user| user.first_name));
+
+            cursor = next;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(names, vec!["Jane", "John", "Richard", "John", "Thomas"]);
+    }
+
+    #[test]
+    fn page_through_nulls() {
+        let db = user_database();
+        let first_pet = || User::query().pets.at(0).name.clone();
+
+        // Thomas and Richard have no pets, and are ordered by insertion among themselves.
+        assert_eq!(
+            page_all(&db, &Q! { User.age > 0 }.order_by(&first_pet(), Asc)),
+            vec!["Thomas", "Richard", "John", "Jane", "John"]
+        );
+
+        assert_eq!(
+            page_all(
+                &db,
+                &Q! { User.age > 0 }
+                    .order_by(&first_pet(), Asc)
+                    .nulls(Nulls::Last)
+            ),
+            vec!["John", "Jane", "John", "Thomas", "Richard"]
+        );
+
+        assert_eq!(
+            page_all(&db, &Q! { User.age > 0 }.order_by(&first_pet(), Desc)),
+            vec!["John", "Jane", "John", "Richard", "Thomas"]
+        );
+
+        assert_eq!(
+            page_all(
+                &db,
+                &Q! { User.age > 0 }
+                    .order_by(&first_pet(), Desc)
+                    .nulls(Nulls::First)
+            ),
+            vec!["Richard", "Thomas", "John", "Jane", "John"]
+        );
+
+        // Nulls within secondary keys are compared the same way.
+        assert_eq!(
+            page_all(
+                &db,
+                &Q! { User.age > 0 }
+                    .order_by(&User::query().last_name, Asc)
+                    .order_by(&first_pet(), Asc)
+            ),
+            vec!["Thomas", "John", "Richard", "John", "Jane"]
+        );
+    }
+
+    #[test]
+    fn page_exactly_full() {
+        let db = user_database();
+        let query = Q! { User.last_name == "Smith" }.order_by(&User::query().age, Asc);
+
+        let (page, cursor) = db.get_page(&query, None, 2).unwrap();
+        assert_eq!(page.len(), 2);
+
+        let (page, cursor) = db.get_page(&query, cursor, 2).unwrap();
+        assert!(page.is_empty());
+        assert!(cursor.is_none());
+    }
+
+    #[test]
+    fn mixed_directions_are_rejected() {
+        let db = user_database();
+        let query = Q! { User.age > 0 }
+            .order_by(&User::query().last_name, Asc)
+            .order_by(&User::query().age, Desc);
+
+        assert!(matches!(
+            db.get_page(&query, None, 2),
+            Err(TransformError::UnsupportedOrdering)
+        ));
+    }
+
+    #[test]
+    fn cursor_from_other_ordering_is_rejected() {
+        let db = user_database();
+        let (_, cursor) = db
+            .get_page(
+                &Q! { User.age > 0 }.order_by(&User::query().age, Asc),
+                None,
+                1,
+            )
+            .unwrap();
+
+        assert!(matches!(
+            db.get_page(&Q! { User.age > 0 }, cursor, 1),
+            Err(TransformError::InvalidCursor)
+        ));
+    }
+}
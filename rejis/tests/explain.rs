@@ -0,0 +1,100 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod explain {
+    use rejis::{
+        filter::{
+            Operator::{Equal, GreaterThan},
+            Or,
+        },
+        Database, Queryable, Table, Q,
+    };
+    use rejis_test_data::{user_database, User};
+    use rusqlite::Connection;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+    struct Account {
+        #[rejis(key, generated)]
+        email: String,
+        tenant: String,
+    }
+
+    fn details<T: rejis::transform::Transform>(db: &Connection, transform: &T) -> Vec<String> {
+        db.explain(transform)
+            .unwrap()
+            .into_iter()
+            .map(|step| step.detail)
+            .collect()
+    }
+
+    #[test]
+    fn explain_scan() {
+        let db = user_database();
+
+        let plan = db.explain(&Q! { User.age > 30 }).unwrap();
+
+        assert_eq!(
+            plan.iter()
+                .map(|step| (step.parent, step.detail.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(0, "SCAN user")]
+        );
+    }
+
+    #[test]
+    fn explain_nested() {
+        let db = user_database();
+
+        let plan = db
+            .explain(&Or(Q! { User.age > 30 }, Q! { User.age < 20 }))
+            .unwrap();
+
+        assert_eq!(plan[0].detail, "COMPOUND QUERY");
+        assert_eq!(plan[0].parent, 0);
+        assert_eq!(
+            plan.iter()
+                .filter(|step| step.detail == "SCAN user")
+                .count(),
+            2
+        );
+
+        // Nested steps refer to the id of their parent step.
+        assert!(plan.iter().any(|step| step.parent != 0));
+        assert!(plan
+            .iter()
+            .filter(|step| step.parent != 0)
+            .all(|step| plan.iter().any(|parent| parent.id == step.parent)));
+    }
+
+    #[test]
+    fn explain_index() {
+        let db = user_database();
+        db.create_index(&Q! { User.first_name }).unwrap();
+
+        assert!(
+            details(&db, &User::query().first_name.cmp(Equal, "John")).contains(&String::from(
+                "SEARCH user USING INDEX user$.first_name (<expr>=?)"
+            ))
+        );
+
+        assert!(
+            details(&db, &User::query().first_name.cmp(GreaterThan, "J")).contains(&String::from(
+                "SEARCH user USING INDEX user$.first_name (<expr>>?)"
+            ))
+        );
+
+        // Filters over other fields are unaffected by the index.
+        assert_eq!(details(&db, &Q! { User.age > 30 })[0], "SCAN user");
+    }
+
+    #[test]
+    fn explain_generated_key() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Account>().unwrap();
+
+        assert!(
+            details(&db, &Account::query().email.cmp(Equal, "john@example.com")).contains(
+                &String::from("SEARCH account USING INDEX account_key (email=?)")
+            )
+        );
+    }
+}
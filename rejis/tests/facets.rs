@@ -0,0 +1,153 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod facets {
+    use rejis::{transform::Transform, Database, Queryable, Table, Q};
+    use rejis_test_data::{user_database, User};
+    use rusqlite::Connection;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Queryable, Serialize, Deserialize, Debug, Clone)]
+    struct Check {
+        passed: bool,
+    }
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+    struct Build {
+        number: u8,
+        checks: Vec<Check>,
+    }
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+    struct Article {
+        title: String,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn count_array_values() {
+        let db = user_database();
+
+        let counts = db
+            .facets(
+                &Q! { User.age > 0 },
+                &[User::query().pets.facet(|pet| pet.name.clone())],
+            )
+            .unwrap();
+
+        assert_eq!(
+            counts,
+            vec![vec![
+                (json!("Jimmy"), 2),
+                (json!("Garfield"), 1),
+                (json!("Lucky"), 1),
+            ]]
+        );
+    }
+
+    #[test]
+    fn multiple_facets() {
+        let db = user_database();
+
+        let counts = db
+            .facets(
+                &Q! { User.last_name == "Smith" },
+                &[
+                    User::query().pets.facet(|pet| pet.name.clone()),
+                    User::query().pets.facet(|pet| pet),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0][0], (json!("Garfield"), 1));
+        assert_eq!(counts[1][2], (json!({ "name": "Lucky" }), 1));
+    }
+
+    #[test]
+    fn facets_of_limited_filter() {
+        let db = user_database();
+
+        let counts = db
+            .facets(
+                &Q! { User.pets[..].name == "Jimmy" }.limit(1),
+                &[User::query().pets.facet(|pet| pet.name.clone())],
+            )
+            .unwrap();
+
+        assert_eq!(counts, vec![vec![(json!("Jimmy"), 1)]]);
+    }
+
+    #[test]
+    fn facets_without_matches() {
+        let db = user_database();
+
+        let counts = db
+            .facets(
+                &Q! { User.age > 100 },
+                &[User::query().pets.facet(|pet| pet.name.clone())],
+            )
+            .unwrap();
+
+        assert_eq!(counts, vec![vec![]]);
+    }
+
+    #[test]
+    fn bool_facets() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Build>().unwrap();
+
+        for (number, checks) in [vec![true, true], vec![true, false], vec![false]]
+            .into_iter()
+            .enumerate()
+        {
+            db.insert(&Build {
+                number: number as u8,
+                checks: checks.into_iter().map(|passed| Check { passed }).collect(),
+            })
+            .unwrap();
+        }
+
+        let counts = db
+            .facets(
+                &Q! { Build.number >= 0 },
+                &[Build::query().checks.facet(|check| check.passed.clone())],
+            )
+            .unwrap();
+
+        assert_eq!(counts, vec![vec![(json!(false), 2), (json!(true), 2)]]);
+    }
+
+    #[test]
+    fn scalar_array_facets() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Article>().unwrap();
+
+        let article = |title: &str, tags: &[&str]| Article {
+            title: title.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        };
+
+        db.insert_many(&[
+            article("Indexing", &["sqlite", "performance"]),
+            article("Migrations", &["sqlite"]),
+            article("Profiling", &["performance", "rust"]),
+        ])
+        .unwrap();
+
+        let counts = db
+            .facets(
+                &Q! { Article.title != "" },
+                &[Article::query().tags.facet(|tag| tag)],
+            )
+            .unwrap();
+
+        assert_eq!(
+            counts,
+            vec![vec![
+                (json!("performance"), 2),
+                (json!("sqlite"), 2),
+                (json!("rust"), 1),
+            ]]
+        );
+    }
+}
@@ -3,6 +3,7 @@ mod filtering {
     use rejis::{
         filter::And,
         filter::Operator::{Equal, Like, NotEqual},
+        transform::TransformError,
         Database, Table,
     };
     use rejis_test_data::{user_database, User};
@@ -95,4 +96,58 @@ mod filtering {
 
         println!("{:#?}", garfield_owners);
     }
+
+    #[test]
+    fn counting() {
+        let db = user_database();
+
+        let johns = db
+            .count(&User::query().first_name.cmp(Equal, "John"))
+            .unwrap();
+        assert_eq!(johns, 2);
+
+        let bobbies = db
+            .count(&User::query().first_name.cmp(Equal, "Bobby"))
+            .unwrap();
+        assert_eq!(bobbies, 0);
+    }
+
+    #[test]
+    fn existence() {
+        let db = user_database();
+
+        assert!(db
+            .exists(&User::query().first_name.cmp(Equal, "John"))
+            .unwrap());
+
+        assert!(!db
+            .exists(&User::query().first_name.cmp(Equal, "Bobby"))
+            .unwrap());
+    }
+
+    #[test]
+    fn cardinality() {
+        let db = user_database();
+
+        let johns = User::query().first_name.cmp(Equal, "John");
+        let janes = User::query().first_name.cmp(Equal, "Jane");
+        let bobbies = User::query().first_name.cmp(Equal, "Bobby");
+
+        assert_eq!(db.first(&johns).unwrap().unwrap().first_name, "John");
+        assert!(db.first(&bobbies).unwrap().is_none());
+
+        assert_eq!(db.optional(&janes).unwrap().unwrap().first_name, "Jane");
+        assert!(db.optional(&bobbies).unwrap().is_none());
+        assert!(matches!(
+            db.optional(&johns),
+            Err(TransformError::MultipleResults)
+        ));
+
+        assert_eq!(db.one(&janes).unwrap().first_name, "Jane");
+        assert!(matches!(db.one(&bobbies), Err(TransformError::NotFound)));
+        assert!(matches!(
+            db.one(&johns),
+            Err(TransformError::MultipleResults)
+        ));
+    }
 }
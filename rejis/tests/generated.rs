@@ -0,0 +1,257 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod generated {
+    use rejis::{filter::Operator::Equal, Database, Queryable, Table, Q};
+    use rusqlite::Connection;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct Account {
+        #[rejis(key, generated)]
+        email: String,
+        #[rejis(index, generated)]
+        tenant: String,
+        age: u8,
+    }
+
+    mod previous {
+        use rejis::{Queryable, Table};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+        pub struct Account {
+            pub email: String,
+            pub tenant: String,
+            #[rejis(index, generated)]
+            pub age: u8,
+        }
+    }
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct Ticket {
+        #[rejis(key, generated)]
+        order: u32,
+        #[rejis(index, generated)]
+        group: String,
+    }
+
+    #[derive(Queryable, Serialize, Deserialize, Debug, Clone)]
+    struct Entry {
+        id: u32,
+    }
+
+    impl Table for Entry {
+        const TABLE_NAME: &'static str = "entry";
+
+        fn generated() -> Vec<rejis::Path> {
+            vec![rejis::Path::default().join("ROWID")]
+        }
+    }
+
+    fn account(email: &str, tenant: &str, age: u8) -> Account {
+        Account {
+            email: email.to_string(),
+            tenant: tenant.to_string(),
+            age,
+        }
+    }
+
+    fn accounts() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Account>().unwrap();
+
+        db.insert_many(&[
+            account("john@example.com", "acme", 32),
+            account("jane@example.com", "acme", 28),
+            account("bob@example.com", "initech", 40),
+        ])
+        .unwrap();
+
+        db
+    }
+
+    fn plan(db: &Connection, sql: &str) -> Vec<String> {
+        let mut stmt = db.prepare(&format!("explain query plan {sql}")).unwrap();
+        let rows = stmt.query_map((), |row| row.get(3)).unwrap();
+        rows.collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn generated_columns() {
+        let db = accounts();
+
+        let tenants: Vec<(String, String)> = db
+            .prepare("select email, tenant from account order by email")
+            .unwrap()
+            .query_map((), |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            tenants,
+            vec![
+                (String::from("bob@example.com"), String::from("initech")),
+                (String::from("jane@example.com"), String::from("acme")),
+                (String::from("john@example.com"), String::from("acme")),
+            ]
+        );
+
+        // Indices over generated fields are created over the columns.
+        assert_eq!(
+            plan(&db, "select * from account where tenant = 'acme'"),
+            vec!["SEARCH account USING INDEX account$.tenant (tenant=?)"]
+        );
+    }
+
+    #[test]
+    fn filtering_generated_columns() {
+        let db = accounts();
+
+        let acme = db.get(&Q! { Account.tenant == "acme" }).unwrap();
+        assert_eq!(acme.len(), 2);
+
+        let john = db
+            .one(&Account::query().email.cmp(Equal, "john@example.com"))
+            .unwrap();
+        assert_eq!(john, account("john@example.com", "acme", 32));
+
+        // Fields which are not generated are still extracted from the json.
+        assert_eq!(db.count(&Q! { Account.age == 40 }).unwrap(), 1);
+    }
+
+    #[test]
+    fn upserting_by_generated_key() {
+        let db = accounts();
+
+        db.upsert(&account("john@example.com", "initech", 33))
+            .unwrap();
+
+        assert_eq!(db.count(&Q! { Account.tenant == "acme" }).unwrap(), 1);
+        assert_eq!(db.count(&Q! { Account.tenant == "initech" }).unwrap(), 2);
+    }
+
+    #[test]
+    fn rebuilding_existing_tables() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(
+            "create table account (value text not null, version integer not null default 0) strict;
+            insert into account (value) values ('{\"email\":\"john@example.com\",\"tenant\":\"acme\",\"age\":32}');",
+        )
+        .unwrap();
+
+        db.init::<Account>().unwrap();
+
+        assert_eq!(
+            db.get(&Q! { Account.tenant == "acme" }).unwrap(),
+            vec![account("john@example.com", "acme", 32)]
+        );
+
+        // Initializing again leaves the table in place.
+        db.init::<Account>().unwrap();
+        assert_eq!(db.count(&Q! { Account.tenant == "acme" }).unwrap(), 1);
+    }
+
+    fn schema(db: &Connection) -> Vec<(String, String)> {
+        let mut stmt = db
+            .prepare("select name, sql from sqlite_master where tbl_name = 'account' and type in ('index', 'trigger') order by name")
+            .unwrap();
+        let rows = stmt
+            .query_map((), |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        rows.collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn rebuilding_preserves_indices_and_triggers() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<previous::Account>().unwrap();
+        db.execute_batch(
+            "create index account_email on account(json_extract(value, '$.email'));
+            create table audit (account integer not null) strict;
+            create trigger account_audit after insert on account begin
+                insert into audit (account) values (new.rowid);
+            end;",
+        )
+        .unwrap();
+
+        db.init::<Account>().unwrap();
+        db.insert(&account("john@example.com", "acme", 32)).unwrap();
+
+        assert_eq!(
+            schema(&db),
+            vec![
+                (
+                    String::from("account$.age"),
+                    String::from(
+                        "CREATE INDEX \"account$.age\" on account(json_extract(value, '$.age'))"
+                    )
+                ),
+                (
+                    String::from("account$.tenant"),
+                    String::from("CREATE INDEX \"account$.tenant\" on account(\"tenant\")")
+                ),
+                (
+                    String::from("account_audit"),
+                    String::from(
+                        "CREATE TRIGGER account_audit after insert on account begin
+                insert into audit (account) values (new.rowid);
+            end"
+                    )
+                ),
+                (
+                    String::from("account_email"),
+                    String::from(
+                        "CREATE INDEX account_email on account(json_extract(value, '$.email'))"
+                    )
+                ),
+                (
+                    String::from("account_key"),
+                    String::from("CREATE UNIQUE INDEX account_key on account(\"email\")")
+                ),
+            ]
+        );
+
+        let audited: usize = db
+            .query_row("select count(*) from audit", (), |row| row.get(0))
+            .unwrap();
+        assert_eq!(audited, 1);
+    }
+
+    #[test]
+    fn generated_columns_must_not_collide() {
+        let db = Connection::open_in_memory().unwrap();
+
+        assert!(matches!(
+            db.init::<Entry>(),
+            Err(rusqlite::Error::InvalidColumnName(column)) if column == "ROWID"
+        ));
+    }
+
+    #[test]
+    fn keyword_columns() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Ticket>().unwrap();
+
+        let ticket = |order: u32, group: &str| Ticket {
+            order,
+            group: group.to_string(),
+        };
+
+        db.insert_many(&[ticket(1, "support"), ticket(2, "sales")])
+            .unwrap();
+        db.upsert(&ticket(2, "support")).unwrap();
+
+        assert_eq!(
+            db.get(&Q! { Ticket.group == "support" }).unwrap(),
+            vec![ticket(1, "support"), ticket(2, "support")]
+        );
+        assert_eq!(
+            plan(&db, "select * from ticket where \"group\" = 'support'"),
+            vec!["SEARCH ticket USING INDEX ticket$.group (group=?)"]
+        );
+
+        // Initializing again leaves the table in place.
+        db.init::<Ticket>().unwrap();
+        assert_eq!(db.count(&Q! { Ticket.order > 0u32 }).unwrap(), 2);
+    }
+}
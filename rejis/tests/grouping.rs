@@ -0,0 +1,157 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod grouping {
+    use rejis::{
+        aggregate::{Avg, Count, Max},
+        transform::Transform,
+        Database, Queryable, Table, Q,
+    };
+    use rejis_test_data::{user_database, User};
+    use rusqlite::Connection;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+    struct Task {
+        title: String,
+        done: bool,
+        assignee: Option<String>,
+    }
+
+    fn tasks() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Task>().unwrap();
+
+        for (title, done, assignee) in [
+            ("a", true, Some("jane")),
+            ("b", false, None),
+            ("c", false, Some("jane")),
+        ] {
+            db.insert(&Task {
+                title: title.to_string(),
+                done,
+                assignee: assignee.map(String::from),
+            })
+            .unwrap();
+        }
+
+        // Documents stored without the assignee field at all.
+        db.execute(
+            "insert into task (value) values (json('{\"title\":\"d\",\"done\":true}'))",
+            (),
+        )
+        .unwrap();
+
+        db
+    }
+
+    #[test]
+    fn count_per_group() {
+        let db = user_database();
+
+        let families = db
+            .group_by(
+                &Q! { User.age > 0 },
+                &User::query().last_name,
+                Count(&User::query()),
+            )
+            .unwrap();
+
+        assert_eq!(
+            families,
+            vec![
+                (String::from("Anderson"), 2),
+                (String::from("LaFleur"), 1),
+                (String::from("Smith"), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn aggregate_per_group() {
+        let db = user_database();
+        let everyone = Q! { User.age > 0 };
+
+        let oldest = db
+            .group_by(
+                &everyone,
+                &User::query().first_name,
+                Max(&User::query().age),
+            )
+            .unwrap();
+        assert_eq!(
+            oldest,
+            vec![
+                (String::from("Jane"), Some(35)),
+                (String::from("John"), Some(48)),
+                (String::from("Richard"), Some(36)),
+                (String::from("Thomas"), Some(24)),
+            ]
+        );
+
+        let average = db
+            .group_by(&everyone, &User::query().last_name, Avg(&User::query().age))
+            .unwrap();
+        assert_eq!(average[0], (String::from("Anderson"), Some(36.0)));
+    }
+
+    #[test]
+    fn group_by_numeric_key() {
+        let db = user_database();
+
+        let pets = db
+            .group_by(
+                &Q! { User.age > 0 },
+                &User::query().age,
+                Count(&User::query().pets.at(0)),
+            )
+            .unwrap();
+
+        assert_eq!(pets, vec![(24, 0), (32, 1), (35, 1), (36, 0), (48, 1)]);
+    }
+
+    #[test]
+    fn group_limited() {
+        let db = user_database();
+
+        let families = db
+            .group_by(
+                &Q! { User.age > 0 }.limit(2),
+                &User::query().last_name,
+                Count(&User::query()),
+            )
+            .unwrap();
+
+        assert_eq!(families, vec![(String::from("Smith"), 2)]);
+    }
+
+    #[test]
+    fn group_by_bool_key() {
+        let db = tasks();
+
+        let done = db
+            .group_by(
+                &Q! { Task.title != "" },
+                &Task::query().done,
+                Count(&Task::query()),
+            )
+            .unwrap();
+
+        assert_eq!(done, vec![(false, 2), (true, 2)]);
+    }
+
+    #[test]
+    fn group_by_optional_key() {
+        let db = tasks();
+
+        // Tasks without an assignee are grouped under `None`, while
+        // documents lacking the field entirely are left out.
+        let assignees = db
+            .group_by(
+                &Q! { Task.title != "" },
+                &Task::query().assignee,
+                Count(&Task::query()),
+            )
+            .unwrap();
+
+        assert_eq!(assignees, vec![(None, 1), (Some(String::from("jane")), 2)]);
+    }
+}
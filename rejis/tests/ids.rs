@@ -0,0 +1,113 @@
+#[cfg(feature = "derive")]
+mod ids {
+    use rejis::{filter::Operator::Equal, transform::Transform, Database, Table};
+    use rejis_test_data::{user_database, User};
+
+    fn bobby() -> User {
+        User {
+            first_name: String::from("Bobby"),
+            last_name: String::from("Tables"),
+            age: 8,
+            pets: vec![],
+        }
+    }
+
+    #[test]
+    fn insert_and_get_by_id() {
+        let db = user_database();
+
+        let id = db.insert(&bobby()).unwrap();
+
+        let bobby = db.get_by_id(id).unwrap().unwrap();
+        assert_eq!(bobby.first_name, "Bobby");
+    }
+
+    #[test]
+    fn replace_and_delete_by_id() {
+        let db = user_database();
+
+        let id = db.insert(&bobby()).unwrap();
+
+        let mut robert = bobby();
+        robert.first_name = String::from("Robert");
+        assert_eq!(db.replace_by_id(id, &robert).unwrap(), 1);
+        assert_eq!(db.get_by_id(id).unwrap().unwrap().first_name, "Robert");
+
+        assert_eq!(db.delete_by_id(id).unwrap(), 1);
+        assert!(db.get_by_id(id).unwrap().is_none());
+    }
+
+    #[test]
+    fn get_with_ids() {
+        let db = user_database();
+
+        let id = db.insert(&bobby()).unwrap();
+
+        let bobbies = db
+            .get_with_ids(&User::query().first_name.cmp(Equal, "Bobby"))
+            .unwrap();
+
+        assert_eq!(bobbies.len(), 1);
+        assert_eq!(bobbies[0].0, id);
+        assert_eq!(bobbies[0].1.last_name, "Tables");
+
+        // Ids are also available for mapped queries.
+        let ages = db
+            .get_with_ids(
+                &User::query()
+                    .first_name
+                    .cmp(Equal, "Bobby")
+                    .map(&User::query().age),
+            )
+            .unwrap();
+
+        assert_eq!(ages, vec![(id, 8)]);
+    }
+
+    #[test]
+    fn insert_many() {
+        let db = user_database();
+
+        let bobbies: Vec<_> = (0..10).map(|age| User { age, ..bobby() }).collect();
+
+        let ids = db.insert_many(&bobbies).unwrap();
+        assert_eq!(ids.len(), 10);
+
+        for (id, bobby) in ids.into_iter().zip(bobbies) {
+            assert_eq!(db.get_by_id(id).unwrap().unwrap().age, bobby.age);
+        }
+    }
+
+    #[test]
+    fn insert_many_rolls_back_on_failure() {
+        let db = user_database();
+
+        let before = db
+            .get(&User::query().first_name.cmp(Equal, "Bobby"))
+            .unwrap()
+            .len();
+
+        // Make the batch fail halfway through, by rejecting anyone older than 5.
+        db.execute(
+            "
+create trigger too_old before insert on user
+when json_extract(new.value, '$.age') > 5
+begin
+    select raise(abort, 'too old');
+end",
+            (),
+        )
+        .unwrap();
+
+        let bobbies: Vec<_> = (0..10).map(|age| User { age, ..bobby() }).collect();
+
+        db.insert_many(&bobbies).unwrap_err();
+
+        let after = db
+            .get(&User::query().first_name.cmp(Equal, "Bobby"))
+            .unwrap()
+            .len();
+
+        assert_eq!(before, after);
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod indices {
+    use rejis::{
+        filter::{And, Or},
+        Database, Queryable, Table, Q,
+    };
+    use rejis_test_data::{user_database, User};
+    use rusqlite::Connection;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+    struct Account {
+        #[rejis(key)]
+        email: String,
+        #[rejis(index)]
+        tenant: String,
+        #[rejis(index)]
+        age: u8,
+    }
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+    struct Lock {
+        #[rejis(key)]
+        name: String,
+        #[rejis(index)]
+        key: String,
+        #[rejis(index)]
+        owner_id: u32,
+        owner: Owner,
+    }
+
+    #[derive(Queryable, Serialize, Deserialize, Debug, Clone)]
+    struct Owner {
+        id: u32,
+    }
+
+    fn indices(db: &Connection, table: &str) -> Vec<(String, String)> {
+        let mut stmt = db
+            .prepare("select name, sql from sqlite_master where type = 'index' and tbl_name = ?1 order by name")
+            .unwrap();
+        let rows = stmt
+            .query_map((table,), |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        rows.collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn declared_indices() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Account>().unwrap();
+
+        assert_eq!(
+            indices(&db, "account"),
+            vec![
+                (
+                    String::from("account$.age"),
+                    String::from(
+                        "CREATE INDEX \"account$.age\" on account(json_extract(value, '$.age'))"
+                    )
+                ),
+                (
+                    String::from("account$.tenant"),
+                    String::from(
+                        "CREATE INDEX \"account$.tenant\" on account(json_extract(value, '$.tenant'))"
+                    )
+                ),
+                (
+                    String::from("account_key"),
+                    String::from(
+                        "CREATE UNIQUE INDEX account_key on account(json_extract(value, '$.email'))"
+                    )
+                ),
+            ]
+        );
+
+        // Initializing again leaves the existing indices in place.
+        db.init::<Account>().unwrap();
+        assert_eq!(indices(&db, "account").len(), 3);
+    }
+
+    #[test]
+    fn create_index() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<User>().unwrap();
+
+        db.create_index(&Q! { User.last_name }).unwrap();
+        db.create_index(&User::query().pets.at(0).name).unwrap();
+        db.create_index(&Q! { User.last_name }).unwrap();
+
+        let names: Vec<_> = indices(&db, "user")
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["user$.last_name", "user$.pets[0].name"]);
+    }
+
+    #[test]
+    fn distinct_index_names() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Lock>().unwrap();
+        db.create_index(&Lock::query().owner.id).unwrap();
+
+        let names: Vec<_> = indices(&db, "lock")
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(
+            names,
+            vec!["lock$.key", "lock$.owner.id", "lock$.owner_id", "lock_key"]
+        );
+    }
+
+    fn details<T: rejis::transform::Transform>(db: &Connection, transform: &T) -> Vec<String> {
+        db.explain(transform)
+            .unwrap()
+            .into_iter()
+            .map(|step| step.detail)
+            .collect()
+    }
+
+    #[test]
+    fn indexed_conjunction() {
+        let db = user_database();
+        db.create_index(&Q! { User.first_name }).unwrap();
+
+        let details = details(
+            &db,
+            &And(
+                Q! { User.first_name == "John" },
+                Q! { User.last_name == "Smith" },
+            ),
+        );
+        assert!(details.contains(&String::from(
+            "SEARCH user USING INDEX user$.first_name (<expr>=?)"
+        )));
+        assert!(!details.iter().any(|detail| detail.starts_with("SCAN")));
+    }
+
+    #[test]
+    fn indexed_disjunction() {
+        let db = user_database();
+        db.create_index(&Q! { User.first_name }).unwrap();
+        db.create_index(&Q! { User.last_name }).unwrap();
+
+        let details = details(
+            &db,
+            &Or(
+                Q! { User.first_name == "John" },
+                Q! { User.last_name == "Smith" },
+            ),
+        );
+        assert!(details.contains(&String::from(
+            "SEARCH user USING INDEX user$.first_name (<expr>=?)"
+        )));
+        assert!(details.contains(&String::from(
+            "SEARCH user USING INDEX user$.last_name (<expr>=?)"
+        )));
+        assert!(!details.iter().any(|detail| detail.starts_with("SCAN")));
+    }
+}
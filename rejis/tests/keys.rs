@@ -0,0 +1,88 @@
+#[cfg(feature = "derive")]
+mod keys {
+    use rejis::{filter::Operator::Equal, transform::TransformError, Database, Queryable, Table};
+    use rusqlite::Connection;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+    struct Account {
+        #[rejis(key)]
+        email: String,
+        name: String,
+    }
+
+    fn account_database() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Account>().unwrap();
+
+        db.insert(&Account {
+            email: String::from("john@example.com"),
+            name: String::from("John"),
+        })
+        .unwrap();
+
+        db
+    }
+
+    #[test]
+    fn duplicate_key_insertion() {
+        let db = account_database();
+
+        db.insert(&Account {
+            email: String::from("john@example.com"),
+            name: String::from("Johnny"),
+        })
+        .unwrap_err();
+    }
+
+    #[test]
+    fn upsert_existing_key() {
+        let db = account_database();
+
+        db.upsert(&Account {
+            email: String::from("john@example.com"),
+            name: String::from("Johnny"),
+        })
+        .unwrap();
+
+        let johns = db
+            .get(&Account::query().email.cmp(Equal, "john@example.com"))
+            .unwrap();
+
+        assert_eq!(johns.len(), 1);
+        assert_eq!(johns[0].name, "Johnny");
+    }
+
+    #[test]
+    fn upsert_new_key() {
+        let db = account_database();
+
+        db.upsert(&Account {
+            email: String::from("jane@example.com"),
+            name: String::from("Jane"),
+        })
+        .unwrap();
+
+        let accounts = db
+            .get(&Account::query().email.cmp(Equal, "jane@example.com"))
+            .unwrap();
+
+        assert_eq!(accounts.len(), 1);
+    }
+
+    #[test]
+    fn upsert_without_key() {
+        let db = rejis_test_data::user_database();
+
+        let error = db
+            .upsert(&rejis_test_data::User {
+                first_name: String::from("Bobby"),
+                last_name: String::from("Tables"),
+                age: 8,
+                pets: vec![],
+            })
+            .unwrap_err();
+
+        assert!(matches!(error, TransformError::MissingKey("user")));
+    }
+}
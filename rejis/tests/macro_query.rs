@@ -1,4 +1,5 @@
 #[cfg(all(feature = "macros", feature = "derive"))]
+#[allow(clippy::useless_vec)]
 mod macros {
     use rejis::Database;
     use rejis::Q;
@@ -87,6 +88,18 @@ mod macros {
         assert_eq!(johns.len(), 2);
     }
 
+    #[test]
+    fn path_only_query() {
+        use rejis::transform::Transform;
+        let db = user_database();
+
+        let ages = db
+            .get(&Q! { User.first_name == "John" }.map(&Q! { User.age }))
+            .unwrap();
+
+        assert_eq!(ages.len(), 2);
+    }
+
     #[test]
     fn any_query_literal() {
         let db = user_database();
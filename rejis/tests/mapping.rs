@@ -1,6 +1,11 @@
 #[cfg(feature = "derive")]
 mod mapping {
-    use rejis::{filter::Operator::Equal, transform::Transform, Database, Table};
+    use rejis::{
+        filter::Operator::{Equal, GreaterThan},
+        order::Direction::Asc,
+        transform::{Transform, TransformError},
+        Database, Table,
+    };
     use rejis_test_data::{user_database, User};
 
     #[test]
@@ -20,4 +25,99 @@ mod mapping {
         println!("{ages:?}");
         assert_eq!(ages.len(), 2);
     }
+
+    #[test]
+    fn mapping_into_strings() {
+        let db = user_database();
+
+        let names = db
+            .get(
+                &User::query()
+                    .last_name
+                    .cmp(Equal, "Smith")
+                    .map(&User::query().first_name),
+            )
+            .unwrap();
+
+        assert_eq!(names, vec!["John", "Jane"]);
+    }
+
+    #[test]
+    fn distinct_scalars() {
+        let db = user_database();
+
+        let everyone = || User::query().age.cmp(GreaterThan, 0);
+
+        let last_names = db.get(&everyone().map(&User::query().last_name)).unwrap();
+        assert_eq!(last_names.len(), 5);
+
+        let last_names = db
+            .get(
+                &everyone()
+                    .map(&User::query().last_name)
+                    .distinct()
+                    .order_by(&User::query().last_name, Asc),
+            )
+            .unwrap();
+        assert_eq!(last_names, vec!["Anderson", "LaFleur", "Smith"]);
+
+        let count = db
+            .count(&everyone().map(&User::query().last_name).distinct())
+            .unwrap();
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn distinct_documents() {
+        let db = user_database();
+
+        let pets = db
+            .get(
+                &User::query()
+                    .age
+                    .cmp(GreaterThan, 0)
+                    .map(&User::query().pets)
+                    .distinct(),
+            )
+            .unwrap();
+
+        let mut names: Vec<Vec<String>> = pets
+            .into_iter()
+            .map(|pets| pets.into_iter().map(|pet| pet.name).collect())
+            .collect();
+        names.sort();
+
+        assert_eq!(
+            names,
+            vec![
+                vec![],
+                vec![String::from("Garfield"), String::from("Lucky")],
+                vec![String::from("Jimmy")],
+                vec![String::from("Jimmy"), String::from("Jimmy")],
+            ]
+        );
+    }
+
+    #[test]
+    fn distinct_rows_without_ids() {
+        let db = user_database();
+
+        let last_names = || {
+            User::query()
+                .age
+                .cmp(GreaterThan, 0)
+                .map(&User::query().last_name)
+                .distinct()
+                .order_by(&User::query().last_name, Asc)
+        };
+
+        assert!(matches!(
+            db.get_with_ids(&last_names()),
+            Err(TransformError::UnsupportedDistinct)
+        ));
+        assert!(matches!(
+            db.get_page(&last_names(), None, 2),
+            Err(TransformError::UnsupportedDistinct)
+        ));
+    }
 }
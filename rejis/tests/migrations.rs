@@ -0,0 +1,218 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod migrations {
+    use rejis::{filter::Operator::Equal, Database, Table};
+    use rusqlite::Connection;
+    use serde_json::Value;
+
+    mod v1 {
+        use rejis::{Queryable, Table};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+        pub struct Account {
+            pub name: String,
+            pub nickname: String,
+        }
+    }
+
+    mod v2 {
+        use rejis::{migration::Migration, Queryable, Table};
+        use serde::{Deserialize, Serialize};
+        use serde_json::Value;
+
+        #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+        #[rejis(migrations = "migrations")]
+        pub struct Account {
+            pub full_name: String,
+            pub active: bool,
+            pub logins: u32,
+        }
+
+        fn migrations() -> Vec<Migration> {
+            vec![
+                Migration::sql("json_remove(value, '$.nickname')"),
+                Migration::sql("json_set(value, '$.active', json('true'), '$.logins', 0)"),
+                Migration::function(|mut value| {
+                    let name = value["name"].take();
+                    value["full_name"] = name;
+                    value.as_object_mut().unwrap().remove("name");
+                    value
+                }),
+                Migration::function(|mut value| {
+                    value["logins"] = Value::from(value["logins"].as_u64().unwrap() + 1);
+                    value
+                }),
+            ]
+        }
+    }
+
+    mod upgraded {
+        use rejis::{
+            migration::{Migration, Upgrade},
+            Queryable, Table,
+        };
+        use serde::{Deserialize, Serialize};
+        use serde_json::Value;
+
+        #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+        #[rejis(migrations = "migrations", upgrades = "upgrades")]
+        pub struct Account {
+            pub name: String,
+            pub active: bool,
+        }
+
+        fn migrations() -> Vec<Migration> {
+            vec![Migration::function(
+                |_| serde_json::json!({ "name": "Migrated", "active": true }),
+            )]
+        }
+
+        fn upgrades() -> Vec<Upgrade> {
+            vec![|mut value: Value| {
+                value["active"] = Value::from(false);
+                value
+            }]
+        }
+    }
+
+    mod broken {
+        use rejis::{migration::Migration, Queryable, Table};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+        #[rejis(migrations = "migrations")]
+        pub struct Account {
+            pub name: String,
+        }
+
+        fn migrations() -> Vec<Migration> {
+            vec![
+                Migration::sql("json_remove(value, '$.nickname')"),
+                Migration::sql("no_such_function(value)"),
+            ]
+        }
+    }
+
+    fn legacy_database() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<v1::Account>().unwrap();
+
+        db.insert(&v1::Account {
+            name: String::from("John Smith"),
+            nickname: String::from("Johnny"),
+        })
+        .unwrap();
+
+        db.insert(&v1::Account {
+            name: String::from("Jane Doe"),
+            nickname: String::from("Janey"),
+        })
+        .unwrap();
+
+        db
+    }
+
+    fn tracked(db: &Connection) -> bool {
+        db.query_row(
+            "select exists(select 1 from sqlite_master where name = 'rejis_migrations')",
+            (),
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    fn applied(db: &Connection) -> usize {
+        if !tracked(db) {
+            return 0;
+        }
+
+        db.query_row("select count(*) from rejis_migrations", (), |row| {
+            row.get(0)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn apply_pending_migrations() {
+        let db = legacy_database();
+        db.init::<v2::Account>().unwrap();
+
+        let account = db
+            .one(&v2::Account::query().full_name.cmp(Equal, "John Smith"))
+            .unwrap();
+        assert!(account.active);
+        assert_eq!(account.logins, 1);
+        assert_eq!(applied(&db), 4);
+
+        let account = db
+            .one(&v2::Account::query().full_name.cmp(Equal, "Jane Doe"))
+            .unwrap();
+        assert_eq!(account.logins, 1);
+
+        let raw: String = db
+            .query_row("select value from account", (), |row| row.get(0))
+            .unwrap();
+        assert!(serde_json::from_str::<Value>(&raw).unwrap()["nickname"].is_null());
+    }
+
+    #[test]
+    fn untracked_without_migrations() {
+        let db = legacy_database();
+        assert!(!tracked(&db));
+
+        db.init::<v2::Account>().unwrap();
+        assert!(tracked(&db));
+    }
+
+    #[test]
+    fn migrated_objects_are_current() {
+        let db = legacy_database();
+        db.init::<upgraded::Account>().unwrap();
+
+        let accounts = db
+            .get(&upgraded::Account::query().name.cmp(Equal, "Migrated"))
+            .unwrap();
+        assert_eq!(accounts.len(), 2);
+        assert!(accounts.iter().all(|account| account.active));
+
+        let version: usize = db
+            .query_row("select max(version) from account", (), |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, 1);
+    }
+
+    #[test]
+    fn migrations_are_applied_once() {
+        let db = legacy_database();
+        db.init::<v2::Account>().unwrap();
+        db.init::<v2::Account>().unwrap();
+
+        let account = db
+            .one(&v2::Account::query().full_name.cmp(Equal, "John Smith"))
+            .unwrap();
+        assert_eq!(account.logins, 1);
+        assert_eq!(applied(&db), 4);
+    }
+
+    #[test]
+    fn failed_migrations_are_rolled_back() {
+        let db = legacy_database();
+        db.init::<broken::Account>().unwrap_err();
+
+        assert_eq!(applied(&db), 0);
+        let nickname: String = db
+            .query_row(
+                "select json_extract(value, '$.nickname') from account",
+                (),
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(nickname, "Johnny");
+    }
+
+    #[test]
+    fn declared_migrations() {
+        assert!(v1::Account::migrations().is_empty());
+        assert_eq!(v2::Account::migrations().len(), 4);
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod opening {
+    use rejis::{
+        open::{JournalMode, Synchronous},
+        Database, Table, Q,
+    };
+    use rejis_test_data::{Pet, User};
+    use rusqlite::Connection;
+    use std::time::Duration;
+
+    fn pragma(connection: &Connection, name: &str) -> String {
+        connection
+            .query_row(&format!("pragma {name}"), (), |row| {
+                row.get::<_, rusqlite::types::Value>(0)
+            })
+            .map(|value| match value {
+                rusqlite::types::Value::Integer(integer) => integer.to_string(),
+                rusqlite::types::Value::Text(text) => text,
+                other => format!("{other:?}"),
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn default_pragmas() {
+        let db = rejis::open(":memory:").connect().unwrap();
+
+        assert_eq!(pragma(&db, "synchronous"), "1");
+        assert_eq!(pragma(&db, "foreign_keys"), "1");
+        assert_eq!(pragma(&db, "busy_timeout"), "5000");
+    }
+
+    #[test]
+    fn configured_pragmas() {
+        let db = rejis::open(":memory:")
+            .journal_mode(JournalMode::Memory)
+            .synchronous(Synchronous::Extra)
+            .busy_timeout(Duration::from_millis(250))
+            .foreign_keys(false)
+            .mmap_size(1 << 20)
+            .connect()
+            .unwrap();
+
+        assert_eq!(pragma(&db, "journal_mode"), "memory");
+        assert_eq!(pragma(&db, "synchronous"), "3");
+        assert_eq!(pragma(&db, "foreign_keys"), "0");
+        assert_eq!(pragma(&db, "busy_timeout"), "250");
+    }
+
+    #[test]
+    fn initialize_tables() {
+        let db = rejis::open(":memory:").init::<User>().connect().unwrap();
+
+        db.insert(&User {
+            first_name: String::from("Open"),
+            last_name: String::from("Builder"),
+            age: 1,
+            pets: vec![Pet {
+                name: String::from("Rex"),
+            }],
+        })
+        .unwrap();
+
+        assert_eq!(db.count(&Q! { User.age > 0 }).unwrap(), 1);
+    }
+
+    #[test]
+    fn wal_on_disk() {
+        let path =
+            std::env::temp_dir().join(format!("rejis-opening-{pid}.db", pid = std::process::id()));
+
+        let db = rejis::open(&path).connect().unwrap();
+        assert_eq!(pragma(&db, "journal_mode"), "wal");
+        drop(db);
+
+        for suffix in ["", "-wal", "-shm"] {
+            let mut file = path.clone().into_os_string();
+            file.push(suffix);
+            let _ = std::fs::remove_file(file);
+        }
+    }
+}
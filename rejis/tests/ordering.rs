@@ -0,0 +1,110 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod ordering {
+    use rejis::{
+        order::{
+            Direction::{Asc, Desc},
+            Nulls,
+        },
+        transform::Transform,
+        Database, Table, Q,
+    };
+    use rejis_test_data::{user_database, User};
+
+    #[test]
+    fn order_by_single_key() {
+        let db = user_database();
+
+        let ages = db
+            .get(
+                &Q! { User.age > 0 }
+                    .order_by(&User::query().age, Asc)
+                    .map(&User::query().age),
+            )
+            .unwrap();
+        assert_eq!(ages, vec![24, 32, 35, 36, 48]);
+
+        let ages = db
+            .get(
+                &Q! { User.age > 0 }
+                    .map(&User::query().age)
+                    .order_by(&User::query().age, Desc),
+            )
+            .unwrap();
+        assert_eq!(ages, vec![48, 36, 35, 32, 24]);
+    }
+
+    #[test]
+    fn order_by_multiple_keys() {
+        let db = user_database();
+
+        let users = db
+            .get(
+                &Q! { User.age > 0 }
+                    .order_by(&User::query().last_name, Asc)
+                    .order_by(&User::query().age, Desc),
+            )
+            .unwrap();
+
+        let names: Vec<_> = users
+            .iter()
+            .map(|user| format!("{} {}", user.first_name, user.last_name))
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "John Anderson",
+                "Thomas Anderson",
+                "Richard LaFleur",
+                "Jane Smith",
+                "John Smith"
+            ]
+        );
+    }
+
+    #[test]
+    fn order_nulls_last() {
+        let db = user_database();
+
+        let first_pet = User::query().pets.at(0).name.clone();
+
+        let users = db
+            .get(&Q! { User.age > 0 }.order_by(&first_pet, Asc))
+            .unwrap();
+        assert!(users[0].pets.is_empty());
+
+        let users = db
+            .get(
+                &Q! { User.age > 0 }
+                    .order_by(&first_pet, Asc)
+                    .nulls(Nulls::Last),
+            )
+            .unwrap();
+        assert_eq!(users[0].pets[0].name, "Garfield");
+        assert!(users[4].pets.is_empty());
+    }
+
+    #[test]
+    fn order_across_unions() {
+        let db = user_database();
+
+        let oldest = db
+            .first(
+                &Q! { (User.first_name == "Thomas") || (User.last_name == "Smith") }
+                    .order_by(&User::query().age, Desc),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(oldest.first_name, "Jane");
+
+        let youngest_smith = db
+            .get(
+                &Q! { (User.first_name == "Thomas") || (User.last_name == "Smith") }
+                    .order_by(&User::query().age, Asc)
+                    .offset(1)
+                    .limit(1),
+            )
+            .unwrap();
+        assert_eq!(youngest_smith[0].first_name, "John");
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod paging {
+    use rejis::{filter::Operator::GreaterThan, transform::Transform, Database, Table, Q};
+    use rejis_test_data::{user_database, User};
+
+    #[test]
+    fn limit_and_offset() {
+        let db = user_database();
+
+        let everyone = db.get(&User::query().age.cmp(GreaterThan, 0)).unwrap();
+        assert_eq!(everyone.len(), 5);
+
+        let first_two = db
+            .get(&User::query().age.cmp(GreaterThan, 0).limit(2))
+            .unwrap();
+        assert_eq!(first_two.len(), 2);
+        assert_eq!(first_two[0].first_name, everyone[0].first_name);
+
+        let next_two = db
+            .get(&User::query().age.cmp(GreaterThan, 0).offset(2).limit(2))
+            .unwrap();
+        assert_eq!(next_two.len(), 2);
+        assert_eq!(next_two[0].first_name, everyone[2].first_name);
+
+        let remainder = db
+            .get(&User::query().age.cmp(GreaterThan, 0).offset(4))
+            .unwrap();
+        assert_eq!(remainder.len(), 1);
+        assert_eq!(remainder[0].first_name, everyone[4].first_name);
+    }
+
+    #[test]
+    fn chained_limits() {
+        let db = user_database();
+
+        let everyone = || User::query().age.cmp(GreaterThan, 0);
+        let names = |users: Vec<User>| -> Vec<String> {
+            users.into_iter().map(|user| user.first_name).collect()
+        };
+        let all = names(db.get(&everyone()).unwrap());
+
+        // Later limits can only narrow the results of earlier ones.
+        assert_eq!(db.get(&everyone().limit(2).limit(5)).unwrap().len(), 2);
+        assert_eq!(db.get(&everyone().limit(5).limit(2)).unwrap().len(), 2);
+
+        // Offsets accumulate.
+        assert_eq!(
+            names(db.get(&everyone().offset(1).offset(1)).unwrap()),
+            all[2..]
+        );
+
+        // Offsets skip within the results of earlier limits.
+        assert_eq!(
+            names(db.get(&everyone().limit(3).offset(1)).unwrap()),
+            all[1..3]
+        );
+        assert_eq!(
+            names(
+                db.get(&everyone().offset(1).limit(3).offset(1).limit(1))
+                    .unwrap()
+            ),
+            all[2..3]
+        );
+        assert!(db.get(&everyone().limit(2).offset(2)).unwrap().is_empty());
+        assert_eq!(db.count(&everyone().limit(2).offset(1)).unwrap(), 1);
+    }
+
+    #[test]
+    fn limit_composite_filters() {
+        let db = user_database();
+
+        let limited = Q! {
+            (User.first_name == "John") || (User.pets[..].name == "Jimmy")
+        }
+        .limit(2);
+
+        assert_eq!(db.get(&limited).unwrap().len(), 2);
+        assert_eq!(db.count(&limited).unwrap(), 2);
+    }
+
+    #[test]
+    fn limit_mapped_query() {
+        let db = user_database();
+
+        let ages = db
+            .get(
+                &Q! { User.last_name == "Smith" }
+                    .map(&User::query().age)
+                    .limit(1),
+            )
+            .unwrap();
+        assert_eq!(ages, vec![32]);
+
+        let ages = db
+            .get(
+                &Q! { User.last_name == "Smith" }
+                    .offset(1)
+                    .map(&User::query().age),
+            )
+            .unwrap();
+        assert_eq!(ages, vec![35]);
+    }
+
+    #[test]
+    fn limit_deletion() {
+        let db = user_database();
+
+        let johns = Q! { User.first_name == "John" };
+
+        assert_eq!(db.delete(&johns.limit(1)).unwrap(), 1);
+        assert_eq!(db.count(&Q! { User.first_name == "John" }).unwrap(), 1);
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod store {
+    use rejis::{transform::TransformError, Database, Store, Table, Q};
+    use rejis_test_data::{Pet, User};
+    use std::{path::PathBuf, sync::Arc, thread};
+
+    /// Database file which is removed along with its WAL files when dropped.
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("rejis-{name}-{pid}.db", pid = std::process::id()));
+            let file = TempFile(path);
+            file.remove();
+            file
+        }
+
+        fn remove(&self) {
+            for suffix in ["", "-wal", "-shm"] {
+                let mut path = self.0.clone().into_os_string();
+                path.push(suffix);
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            self.remove();
+        }
+    }
+
+    fn user(first_name: &str, age: u8) -> User {
+        User {
+            first_name: String::from(first_name),
+            last_name: String::from("Store"),
+            age,
+            pets: vec![Pet {
+                name: String::from("Rex"),
+            }],
+        }
+    }
+
+    #[test]
+    fn writes_are_visible_to_readers() {
+        let file = TempFile::new("visible");
+        let store = Store::open(&file.0, 2).unwrap();
+        store.init::<User>().unwrap();
+
+        store.insert(&user("Alice", 30)).unwrap();
+        store.insert(&user("Bob", 40)).unwrap();
+        assert_eq!(store.count(&Q! { User.age > 0 }).unwrap(), 2);
+
+        store.delete(&Q! { User.first_name == "Alice" }).unwrap();
+        let remaining = store.get(&Q! { User.age > 0 }).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].first_name, "Bob");
+    }
+
+    #[test]
+    fn failed_writes_are_rolled_back() {
+        let file = TempFile::new("rollback");
+        let store = Store::open(&file.0, 1).unwrap();
+        store.init::<User>().unwrap();
+
+        store
+            .write(|connection| {
+                connection.insert(&user("Alice", 30))?;
+                Err::<(), _>(TransformError::NotFound)
+            })
+            .unwrap_err();
+
+        assert_eq!(store.count(&Q! { User.age > 0 }).unwrap(), 0);
+    }
+
+    #[test]
+    fn readers_cannot_write() {
+        let file = TempFile::new("read-only");
+        let store = Store::open(&file.0, 1).unwrap();
+        store.init::<User>().unwrap();
+
+        store
+            .read(|connection| Ok(connection.execute("delete from user", ())?))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn concurrent_reads_and_writes() {
+        let file = TempFile::new("concurrent");
+        let store = Arc::new(Store::open(&file.0, 4).unwrap());
+        store.init::<User>().unwrap();
+
+        let handles: Vec<_> = (0..8)
+            .map(|thread| {
+                let store = store.clone();
+                thread::spawn(move || {
+                    for age in 0..10 {
+                        store
+                            .insert(&user("Concurrent", thread * 10 + age))
+                            .unwrap();
+                        store.count(&Q! { User.age >= 0 }).unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(store.count(&Q! { User.age >= 0 }).unwrap(), 80);
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(feature = "derive")]
+mod streaming {
+    use rejis::{
+        filter::Operator::{Equal, GreaterThan},
+        transform::{Transform, TransformError},
+        Database, Table,
+    };
+    use rejis_test_data::{user_database, User};
+
+    #[test]
+    fn fold_into_sum() {
+        let db = user_database();
+
+        let total_age = db
+            .try_fold(
+                &User::query()
+                    .last_name
+                    .cmp(Equal, "Smith")
+                    .map(&User::query().age),
+                0u32,
+                |total, age| Ok::<_, TransformError>(total + age as u32),
+            )
+            .unwrap();
+
+        assert_eq!(total_age, 32 + 35);
+    }
+
+    #[test]
+    fn fold_stops_on_error() {
+        let db = user_database();
+
+        #[derive(Debug)]
+        enum Error {
+            Rejis,
+            TooMany,
+        }
+
+        impl From<TransformError> for Error {
+            fn from(_: TransformError) -> Self {
+                Error::Rejis
+            }
+        }
+
+        let mut visited = 0;
+        let result = db.try_fold(&User::query().age.cmp(GreaterThan, 0), (), |_, _| {
+            visited += 1;
+            if visited == 2 {
+                Err(Error::TooMany)
+            } else {
+                Ok(())
+            }
+        });
+
+        assert!(matches!(result, Err(Error::TooMany)));
+        assert_eq!(visited, 2);
+    }
+
+    #[test]
+    fn fold_surfaces_row_errors() {
+        let db = user_database();
+
+        // Corrupt one of the rows, so it no longer deserializes into a `User`
+        db.execute(
+            "update user set value = json_remove(value, '$.age') where json_extract(value, '$.first_name') = 'Jane'",
+            (),
+        )
+        .unwrap();
+
+        let result = db.try_fold(&User::query().last_name.cmp(Equal, "Smith"), 0, |n, _| {
+            Ok::<_, TransformError>(n + 1)
+        });
+
+        assert!(matches!(result, Err(TransformError::Serde(_))));
+        assert!(db
+            .get(&User::query().last_name.cmp(Equal, "Smith"))
+            .is_err());
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(all(feature = "macros", feature = "derive"))]
+mod transactions {
+    use rejis::{transform::TransformError, Database, Queryable, Table, Q};
+    use rejis_test_data::{user_database, User};
+    use rusqlite::Connection;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+    struct Pet {
+        owner: String,
+        name: String,
+    }
+
+    fn adopt(db: &impl Database, owner: &str) -> Result<usize, TransformError> {
+        db.insert(&Pet {
+            owner: String::from(owner),
+            name: String::from("Rex"),
+        })?;
+
+        db.delete(&Q! { User.last_name == owner })
+    }
+
+    #[test]
+    fn commit_on_success() {
+        let db = user_database();
+        db.init::<Pet>().unwrap();
+
+        let deleted = db.transaction(|tx| adopt(tx, "Smith")).unwrap();
+        assert_eq!(deleted, 2);
+
+        assert_eq!(db.count(&Q! { Pet.owner == "Smith" }).unwrap(), 1);
+        assert_eq!(db.count(&Q! { User.last_name == "Smith" }).unwrap(), 0);
+    }
+
+    #[test]
+    fn rollback_on_error() {
+        let db = user_database();
+        db.init::<Pet>().unwrap();
+
+        let result: Result<(), TransformError> = db.transaction(|tx| {
+            adopt(tx, "Smith")?;
+            Err(TransformError::NotFound)
+        });
+        assert!(matches!(result, Err(TransformError::NotFound)));
+
+        assert_eq!(db.count(&Q! { Pet.owner == "Smith" }).unwrap(), 0);
+        assert_eq!(db.count(&Q! { User.last_name == "Smith" }).unwrap(), 2);
+    }
+
+    #[test]
+    fn rollback_on_panic() {
+        let db = user_database();
+        db.init::<Pet>().unwrap();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            db.transaction(|tx| -> Result<(), TransformError> {
+                adopt(tx, "Smith")?;
+                panic!("adoption failed");
+            })
+        }));
+        assert!(result.is_err());
+
+        // The savepoint must not be left open on the connection.
+        assert!(db.is_autocommit());
+        assert_eq!(db.count(&Q! { Pet.owner == "Smith" }).unwrap(), 0);
+        assert_eq!(db.count(&Q! { User.last_name == "Smith" }).unwrap(), 2);
+    }
+
+    #[test]
+    fn nested_rollback() {
+        let db = user_database();
+        db.init::<Pet>().unwrap();
+
+        db.transaction(|tx| {
+            adopt(tx, "Smith")?;
+
+            tx.transaction(|inner| {
+                adopt(inner, "Anderson")?;
+                Err::<(), _>(TransformError::NotFound)
+            })
+            .unwrap_err();
+
+            Ok::<_, TransformError>(())
+        })
+        .unwrap();
+
+        assert_eq!(db.count(&Q! { Pet.name == "Rex" }).unwrap(), 1);
+        assert_eq!(db.count(&Q! { User.last_name == "Anderson" }).unwrap(), 2);
+    }
+
+    #[test]
+    fn rusqlite_transactions() {
+        let mut db = user_database();
+        db.init::<Pet>().unwrap();
+
+        let tx = Connection::transaction(&mut db).unwrap();
+        adopt(&tx, "Smith").unwrap();
+        tx.rollback().unwrap();
+        assert_eq!(db.count(&Q! { User.last_name == "Smith" }).unwrap(), 2);
+
+        let mut tx = Connection::transaction(&mut db).unwrap();
+        let savepoint = tx.savepoint().unwrap();
+        adopt(&savepoint, "Smith").unwrap();
+        savepoint.commit().unwrap();
+        tx.commit().unwrap();
+        assert_eq!(db.count(&Q! { User.last_name == "Smith" }).unwrap(), 0);
+    }
+}
@@ -0,0 +1,247 @@
+#[cfg(feature = "derive")]
+mod updating {
+    use rejis::{
+        filter::{And, Operator::Equal},
+        transform::Transform,
+        Database, Queryable, Table,
+    };
+    use rejis_test_data::{user_database, User};
+    use rusqlite::Connection;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct Account {
+        #[rejis(key)]
+        email: String,
+        active: bool,
+        nickname: Option<String>,
+    }
+
+    #[test]
+    fn replace_entire_entry() {
+        let db = user_database();
+
+        let john_smith = And(
+            User::query().first_name.cmp(Equal, "John"),
+            User::query().last_name.cmp(Equal, "Smith"),
+        );
+
+        let replaced = db
+            .replace(
+                &john_smith,
+                &User {
+                    first_name: String::from("Jane"),
+                    last_name: String::from("Doe"),
+                    age: 27,
+                    pets: vec![],
+                },
+            )
+            .unwrap();
+
+        assert_eq!(replaced, 1);
+        assert_eq!(db.get(&john_smith).unwrap().len(), 0);
+
+        let does = db.get(&User::query().last_name.cmp(Equal, "Doe")).unwrap();
+
+        assert_eq!(does.len(), 1);
+        assert_eq!(does[0].first_name, "Jane");
+        assert_eq!(does[0].age, 27);
+    }
+
+    #[test]
+    fn replace_without_matches() {
+        let db = user_database();
+
+        let replaced = db
+            .replace(
+                &User::query().first_name.cmp(Equal, "Bobby"),
+                &User {
+                    first_name: String::from("Jane"),
+                    last_name: String::from("Doe"),
+                    age: 27,
+                    pets: vec![],
+                },
+            )
+            .unwrap();
+
+        assert_eq!(replaced, 0);
+        assert_eq!(
+            db.get(&User::query().last_name.cmp(Equal, "Doe"))
+                .unwrap()
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn modify_single_field() {
+        let db = user_database();
+
+        let johns = User::query().first_name.cmp(Equal, "John");
+
+        let modified = db
+            .modify(&johns, &User::query().last_name, |last_name| {
+                last_name.to_uppercase()
+            })
+            .unwrap();
+
+        assert_eq!(modified, 2);
+
+        let johns = db.get(&johns).unwrap();
+        assert!(johns
+            .iter()
+            .all(|john| john.last_name == john.last_name.to_uppercase()));
+
+        // Other fields must be left untouched.
+        assert!(johns.iter().any(|john| john.pets.len() == 2));
+    }
+
+    #[test]
+    fn modify_is_atomic() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Account>().unwrap();
+
+        for email in ["a@example.com", "b@example.com"] {
+            db.insert(&Account {
+                email: email.to_string(),
+                active: true,
+                nickname: None,
+            })
+            .unwrap();
+        }
+
+        // The second modification violates the unique key, which
+        // must also undo the first one.
+        let result = db.modify(
+            &Account::query().active.cmp(Equal, true),
+            &Account::query().email,
+            |_| String::from("c@example.com"),
+        );
+        assert!(result.is_err());
+
+        assert_eq!(
+            db.count(&Account::query().email.cmp(Equal, "c@example.com"))
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn modify_nested_field() {
+        let db = user_database();
+
+        let modified = db
+            .modify(
+                &User::query().first_name.cmp(Equal, "Jane"),
+                &User::query().pets,
+                |mut pets| {
+                    pets.retain(|pet| pet.name != "Jimmy");
+                    pets
+                },
+            )
+            .unwrap();
+
+        assert_eq!(modified, 1);
+
+        let jane = db
+            .get(&User::query().first_name.cmp(Equal, "Jane"))
+            .unwrap();
+        assert!(jane[0].pets.is_empty());
+    }
+
+    #[test]
+    fn set_single_field() {
+        let db = user_database();
+
+        let johns = User::query().first_name.cmp(Equal, "John");
+
+        let updated = db.update(&johns, &User::query().age.set(30)).unwrap();
+        assert_eq!(updated, 2);
+
+        let johns = db.get(&johns).unwrap();
+        assert!(johns.iter().all(|john| john.age == 30));
+
+        // Non-matching rows must be left untouched.
+        let jane = db
+            .get(&User::query().first_name.cmp(Equal, "Jane"))
+            .unwrap();
+        assert_eq!(jane[0].age, 35);
+    }
+
+    #[test]
+    fn set_string_field() {
+        let db = user_database();
+
+        db.update(
+            &User::query().last_name.cmp(Equal, "Smith"),
+            &User::query().last_name.set("Smyth"),
+        )
+        .unwrap();
+
+        let smyths = db
+            .get(&User::query().last_name.cmp(Equal, "Smyth"))
+            .unwrap();
+        assert_eq!(smyths.len(), 2);
+    }
+
+    #[test]
+    fn set_json_values() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Account>().unwrap();
+
+        db.insert(&Account {
+            email: String::from("a@example.com"),
+            active: true,
+            nickname: None,
+        })
+        .unwrap();
+
+        let account = || Account::query().email.cmp(Equal, "a@example.com");
+
+        db.update(&account(), &Account::query().active.set(false))
+            .unwrap();
+        db.update(
+            &account(),
+            &Account::query().nickname.set(Some(String::from("A"))),
+        )
+        .unwrap();
+
+        assert_eq!(
+            db.one(&account()).unwrap(),
+            Account {
+                email: String::from("a@example.com"),
+                active: false,
+                nickname: Some(String::from("A")),
+            }
+        );
+
+        db.update(&account(), &Account::query().nickname.set(None))
+            .unwrap();
+        assert_eq!(db.one(&account()).unwrap().nickname, None);
+    }
+
+    #[test]
+    fn modify_and_map_bools() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<Account>().unwrap();
+
+        db.insert(&Account {
+            email: String::from("a@example.com"),
+            active: true,
+            nickname: None,
+        })
+        .unwrap();
+
+        let everyone = || Account::query().email.cmp(Equal, "a@example.com");
+
+        db.modify(&everyone(), &Account::query().active, |active| !active)
+            .unwrap();
+        assert!(!db.one(&everyone()).unwrap().active);
+
+        let active = db.get(&everyone().map(&Account::query().active)).unwrap();
+        assert_eq!(active, vec![false]);
+
+        let nicknames = db.get(&everyone().map(&Account::query().nickname)).unwrap();
+        assert_eq!(nicknames, vec![None]);
+    }
+}
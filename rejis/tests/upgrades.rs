@@ -0,0 +1,216 @@
+#[cfg(feature = "derive")]
+mod upgrades {
+    use rejis::{
+        filter::Operator::{Equal, GreaterThan},
+        Database, Table,
+    };
+    use rusqlite::Connection;
+    use serde_json::Value;
+
+    mod v1 {
+        use rejis::{Queryable, Table};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+        pub struct Account {
+            pub id: u32,
+            pub name: String,
+            pub nickname: String,
+        }
+    }
+
+    fn rename_name(mut value: Value) -> Value {
+        let object = value.as_object_mut().unwrap();
+        let name = object.remove("name").unwrap();
+        object.insert(String::from("full_name"), name);
+        value
+    }
+
+    fn drop_nickname(mut value: Value) -> Value {
+        value.as_object_mut().unwrap().remove("nickname");
+        value
+    }
+
+    mod v2 {
+        use rejis::{migration::Upgrade, Queryable, Table};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Queryable, Table, Serialize, Deserialize, Debug, Clone)]
+        #[rejis(upgrades = "upgrades")]
+        pub struct Account {
+            pub id: u32,
+            pub full_name: String,
+        }
+
+        fn upgrades() -> Vec<Upgrade> {
+            vec![super::rename_name, super::drop_nickname]
+        }
+    }
+
+    fn legacy_database() -> Connection {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<v1::Account>().unwrap();
+
+        db.insert(&v1::Account {
+            id: 1,
+            name: String::from("John Smith"),
+            nickname: String::from("Johnny"),
+        })
+        .unwrap();
+
+        db
+    }
+
+    fn stored(db: &Connection) -> Vec<(usize, Value)> {
+        let mut stmt = db
+            .prepare("select version, value from account order by rowid")
+            .unwrap();
+        let rows = stmt
+            .query_map((), |row| {
+                let value: String = row.get(1)?;
+                Ok((row.get(0)?, serde_json::from_str(&value).unwrap()))
+            })
+            .unwrap();
+        rows.collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn upgrade_on_read() {
+        let db = legacy_database();
+        db.init::<v2::Account>().unwrap();
+
+        db.insert(&v2::Account {
+            id: 2,
+            full_name: String::from("Jane Smith"),
+        })
+        .unwrap();
+
+        let accounts = db
+            .get(&v2::Account::query().id.cmp(GreaterThan, 0u32))
+            .unwrap();
+        assert_eq!(accounts[0].full_name, "John Smith");
+        assert_eq!(accounts[1].full_name, "Jane Smith");
+
+        // Without write-back, the stored form is left untouched.
+        let stored = stored(&db);
+        assert_eq!(stored[0].0, 0);
+        assert_eq!(stored[0].1["nickname"], "Johnny");
+        assert_eq!(stored[1].0, 2);
+    }
+
+    #[test]
+    fn upgrade_by_id() {
+        let db = Connection::open_in_memory().unwrap();
+        db.init::<v1::Account>().unwrap();
+
+        let id = db
+            .insert(&v1::Account {
+                id: 1,
+                name: String::from("John Smith"),
+                nickname: String::from("Johnny"),
+            })
+            .unwrap();
+
+        let account = db
+            .get_by_id(rejis::Id::<v2::Account>::new(id.rowid()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.full_name, "John Smith");
+    }
+
+    #[test]
+    fn upgrade_all() {
+        let db = legacy_database();
+        db.init::<v2::Account>().unwrap();
+
+        let accounts = db
+            .get(&v2::Account::query().id.cmp(GreaterThan, 0u32))
+            .unwrap();
+        assert_eq!(accounts[0].full_name, "John Smith");
+
+        // Reading never writes back the upgraded form.
+        assert_eq!(stored(&db)[0].0, 0);
+
+        assert_eq!(db.upgrade_all::<v2::Account>().unwrap(), 1);
+        assert_eq!(db.upgrade_all::<v2::Account>().unwrap(), 0);
+
+        let stored = stored(&db);
+        assert_eq!(stored[0].0, 2);
+        assert_eq!(
+            stored[0].1,
+            serde_json::json!({ "id": 1, "full_name": "John Smith" })
+        );
+
+        let accounts = db
+            .get(&v2::Account::query().full_name.cmp(Equal, "John Smith"))
+            .unwrap();
+        assert_eq!(accounts.len(), 1);
+    }
+
+    #[test]
+    fn writes_stamp_current_version() {
+        let db = legacy_database();
+        db.init::<v2::Account>().unwrap();
+
+        let filter = v2::Account::query().id.cmp(Equal, 1u32);
+        db.modify(&filter, &v2::Account::query().full_name, |name| {
+            name.to_uppercase()
+        })
+        .unwrap();
+
+        let modified = stored(&db);
+        assert_eq!(modified[0].0, 2);
+        assert_eq!(
+            modified[0].1,
+            serde_json::json!({ "id": 1, "full_name": "JOHN SMITH" })
+        );
+
+        let db = legacy_database();
+        db.init::<v2::Account>().unwrap();
+
+        db.update(
+            &v2::Account::query().id.cmp(Equal, 1u32),
+            &v2::Account::query()
+                .full_name
+                .set(String::from("Jane Smith")),
+        )
+        .unwrap();
+
+        let stored = stored(&db);
+        assert_eq!(stored[0].0, 2);
+        assert_eq!(
+            stored[0].1,
+            serde_json::json!({ "id": 1, "full_name": "Jane Smith" })
+        );
+    }
+
+    #[test]
+    fn explicit_upgrade() {
+        let db = legacy_database();
+        db.init::<v2::Account>().unwrap();
+
+        let filter = v2::Account::query().id.cmp(GreaterThan, 0u32);
+        assert_eq!(db.upgrade(&filter).unwrap(), 1);
+        assert_eq!(db.upgrade(&filter).unwrap(), 0);
+        assert_eq!(stored(&db)[0].0, 2);
+    }
+
+    #[test]
+    fn version_column_added_to_existing_tables() {
+        let db = Connection::open_in_memory().unwrap();
+        db.execute_batch(
+            r#"
+            create table account (value text not null) strict;
+            insert into account(value) values('{"id":1,"name":"John Smith","nickname":"Johnny"}');
+            "#,
+        )
+        .unwrap();
+
+        db.init::<v2::Account>().unwrap();
+
+        let accounts = db
+            .get(&v2::Account::query().id.cmp(GreaterThan, 0u32))
+            .unwrap();
+        assert_eq!(accounts[0].full_name, "John Smith");
+    }
+}